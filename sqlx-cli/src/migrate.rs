@@ -1,8 +1,12 @@
+use crate::config::diagnostics::Diagnostics;
 use crate::config::Config;
-use crate::opt::{AddMigrationOpts, ConnectOpts, MigrationSourceOpt};
+use crate::opt::{report_diagnostics, AddMigrationOpts, ConnectOpts, MigrationSourceOpt};
 use anyhow::{bail, Context};
 use console::style;
-use sqlx::migrate::{AppliedMigration, Migrate, MigrateError, MigrationType, Migrator};
+use sqlx::migrate::{
+    check_installed_on_type, check_out_of_order, AppliedMigration, Migrate, MigrateError,
+    MigrationType, Migrator,
+};
 use sqlx::Connection;
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
@@ -115,14 +119,56 @@ fn create_file(
     Ok(())
 }
 
-async fn ensure_migration_schemas(conn: &mut sqlx::AnyConnection, config: &Config) -> anyhow::Result<()> {
+/// The maximum number of schemas created concurrently if `parallel-schema-creation` is enabled.
+const MAX_SCHEMA_CREATION_CONCURRENCY: usize = 4;
+
+/// Create the configured schemas.
+///
+/// If `concurrent` is `false`, schemas are always created one at a time on `conn`,
+/// so no extra connections are opened.
+async fn ensure_migration_schemas(
+    conn: &mut sqlx::AnyConnection,
+    config: &Config,
+    connect_opts: &ConnectOpts,
+    concurrent: bool,
+) -> anyhow::Result<()> {
     use sqlx::migrate::Migrate;
-    
-    // Create all configured schemas
-    for schema_name in &config.migrate.create_schemas {
-        conn.create_schema_if_not_exists(schema_name).await?;
+
+    // Schemas created on one connection must be visible to the others,
+    // which isn't guaranteed for SQLite (e.g. in-memory databases).
+    let max_concurrency = match conn.backend_name() {
+        "PostgreSQL" | "MySQL" if concurrent => MAX_SCHEMA_CREATION_CONCURRENCY,
+        _ => 1,
+    };
+
+    // Extra connections for creating schemas concurrently, opened as needed.
+    let mut extra_conns = Vec::new();
+
+    // Create all configured schemas, in order
+    for batch in config.migrate.create_schemas_batches(max_concurrency)? {
+        let Some((first, rest)) = batch.split_first() else {
+            continue;
+        };
+
+        while extra_conns.len() < rest.len() {
+            extra_conns.push(crate::connect(config, connect_opts).await?);
+        }
+
+        let creates = std::iter::once(conn.create_schema_if_not_exists(first)).chain(
+            rest.iter()
+                .zip(&mut extra_conns)
+                .map(|(schema_name, extra_conn)| {
+                    extra_conn.create_schema_if_not_exists(schema_name)
+                }),
+        );
+
+        futures_util::future::try_join_all(creates).await?;
+    }
+
+    for extra_conn in extra_conns {
+        let _ = extra_conn.close().await;
     }
-    
+
     // Also create the schema for PostgreSQL if specified
     if conn.backend_name() == "PostgreSQL" {
         if let Some(schema) = config.migrate.postgres_schema() {
@@ -151,11 +197,22 @@ pub async fn info(
     let mut conn = crate::connect(config, connect_opts).await?;
 
     // FIXME: we shouldn't actually be creating anything here
-    ensure_migration_schemas(&mut conn, &config).await?;
+    ensure_migration_schemas(&mut conn, config, connect_opts, false).await?;
 
     let table_name = config.migrate.table_name();
 
-    conn.ensure_migrations_table(&table_name).await?;
+    conn.ensure_migrations_table_with(&table_name, config.migrate.installed_on_type)
+        .await?;
+
+    let mut diagnostics = Diagnostics::new();
+    check_installed_on_type(
+        &mut conn,
+        &table_name,
+        config.migrate.installed_on_type,
+        &mut diagnostics,
+    )
+    .await?;
+    report_diagnostics(&diagnostics)?;
 
     let applied_migrations: HashMap<_, _> = conn
         .list_applied_migrations(&table_name)
@@ -249,9 +306,19 @@ pub async fn run(
     let mut conn = crate::connect(config, connect_opts).await?;
     let table_name = config.migrate.table_name();
 
-    ensure_migration_schemas(&mut conn, &config).await?;
+    ensure_migration_schemas(&mut conn, config, connect_opts, true).await?;
+
+    conn.ensure_migrations_table_with(&table_name, config.migrate.installed_on_type)
+        .await?;
 
-    conn.ensure_migrations_table(&table_name).await?;
+    let mut diagnostics = Diagnostics::new();
+    check_installed_on_type(
+        &mut conn,
+        &table_name,
+        config.migrate.installed_on_type,
+        &mut diagnostics,
+    )
+    .await?;
 
     let version = conn.dirty_version(&table_name).await?;
     if let Some(version) = version {
@@ -260,6 +327,7 @@ pub async fn run(
 
     let applied_migrations = conn.list_applied_migrations(&table_name).await?;
     validate_applied_migrations(&applied_migrations, &migrator, ignore_missing)?;
+    check_out_of_order(migrator.iter(), &applied_migrations, &mut diagnostics);
 
     let latest_version = applied_migrations
         .iter()
@@ -277,6 +345,8 @@ pub async fn run(
         .map(|m| (m.version, m))
         .collect();
 
+    report_diagnostics(&diagnostics)?;
+
     for migration in migrator.iter() {
         if migration.migration_type.is_down_migration() {
             // Skipping down migrations
@@ -296,7 +366,8 @@ pub async fn run(
                 let elapsed = if dry_run || skip {
                     Duration::new(0, 0)
                 } else {
-                    conn.apply(&table_name, migration).await?
+                    conn.apply_with(&table_name, migration, config.migrate.installed_on_type)
+                        .await?
                 };
                 let text = if skip {
                     "Skipped"
@@ -348,9 +419,20 @@ pub async fn revert(
     let table_name = config.migrate.table_name();
 
     // FIXME: we should not be creating anything here if it doesn't exist
-    ensure_migration_schemas(&mut conn, &config).await?;
-
-    conn.ensure_migrations_table(&table_name).await?;
+    ensure_migration_schemas(&mut conn, config, connect_opts, false).await?;
+
+    conn.ensure_migrations_table_with(&table_name, config.migrate.installed_on_type)
+        .await?;
+
+    let mut diagnostics = Diagnostics::new();
+    check_installed_on_type(
+        &mut conn,
+        &table_name,
+        config.migrate.installed_on_type,
+        &mut diagnostics,
+    )
+    .await?;
+    report_diagnostics(&diagnostics)?;
 
     let version = conn.dirty_version(&table_name).await?;
     if let Some(version) = version {
@@ -1,3 +1,4 @@
+use crate::config::diagnostics::{Diagnostics, Severity};
 use crate::config::migrate::{DefaultMigrationType, DefaultVersioning};
 use crate::config::Config;
 use anyhow::Context;
@@ -8,7 +9,8 @@ use clap::{
 };
 #[cfg(feature = "completions")]
 use clap_complete::Shell;
-use sqlx::migrate::{MigrateError, Migrator, ResolveWith};
+use console::style;
+use sqlx::migrate::{resolve_blocking_with_diagnostics, MigrateError, Migrator, VersioningScheme};
 use std::env;
 use std::ops::{Deref, Not};
 use std::path::PathBuf;
@@ -350,12 +352,32 @@ impl MigrationSourceOpt {
         config.migrate.migrations_dir()
     }
 
-    pub async fn resolve(&self, config: &Config) -> Result<Migrator, MigrateError> {
-        Migrator::new(ResolveWith(
-            self.resolve_path(config),
-            config.migrate.to_resolve_config(),
-        ))
+    pub async fn resolve(&self, config: &Config) -> anyhow::Result<Migrator> {
+        let path = PathBuf::from(self.resolve_path(config));
+        let resolve_config = config.migrate.to_resolve_config();
+
+        let (res, diagnostics) = tokio::task::spawn_blocking(move || {
+            let mut diagnostics = Diagnostics::new();
+            let res = resolve_blocking_with_diagnostics(&path, &resolve_config, &mut diagnostics);
+            (res, diagnostics)
+        })
         .await
+        .context("unexpected error resolving migrations")?;
+
+        let migrations = match res {
+            Ok(migrations) => migrations,
+            Err(e) => {
+                // Warnings collected before the error may explain it, e.g. a skipped file.
+                print_diagnostics(&diagnostics);
+                return Err(MigrateError::Source(e.into()).into());
+            }
+        };
+
+        report_diagnostics(&diagnostics)?;
+
+        Ok(Migrator::with_migrations(
+            migrations.into_iter().map(|(m, _p)| m).collect(),
+        ))
     }
 }
 
@@ -459,7 +481,7 @@ impl ConfigOpt {
         let path = self.config.clone();
 
         // Tokio does file I/O on a background task anyway
-        tokio::task::spawn_blocking(|| {
+        let config = tokio::task::spawn_blocking(|| {
             if let Some(path) = path {
                 let err_str = format!("error reading config from {path:?}");
                 Config::try_from_path(path).context(err_str)
@@ -475,7 +497,38 @@ impl ConfigOpt {
             }
         })
         .await
-        .context("unexpected error loading config")?
+        .context("unexpected error loading config")??;
+
+        report_diagnostics(&config.validate())?;
+
+        Ok(config)
+    }
+}
+
+/// Print all diagnostics to stderr, then fail if any of them are errors.
+pub(crate) fn report_diagnostics(diagnostics: &Diagnostics) -> anyhow::Result<()> {
+    print_diagnostics(diagnostics);
+
+    if diagnostics.has_errors() {
+        anyhow::bail!("configuration or migrations have errors; see above");
+    }
+
+    Ok(())
+}
+
+fn print_diagnostics(diagnostics: &Diagnostics) {
+    for diagnostic in diagnostics {
+        let severity = match diagnostic.severity {
+            Severity::Warning => style(diagnostic.severity).yellow(),
+            Severity::Error => style(diagnostic.severity).red(),
+        };
+
+        eprint!("{}: {}", severity.bold(), diagnostic.message);
+
+        match &diagnostic.location {
+            Some(location) => eprintln!(" {}", style(format!("(at {location})")).dim()),
+            None => eprintln!(),
+        }
     }
 }
 
@@ -543,25 +596,17 @@ impl AddMigrationOpts {
                     .map_or(1, |migration| migration.version + 1),
             ),
             (false, false, DefaultVersioning::Inferred) => {
-                migrator
-                    .migrations
-                    .rchunks(2)
-                    .next()
-                    .and_then(|migrations| {
-                        match migrations {
-                            [previous, latest] => {
-                                // If the latest two versions differ by 1, infer sequential.
-                                (latest.version - previous.version == 1)
-                                    .then_some(latest.version + 1)
-                            }
-                            [latest] => {
-                                // If only one migration exists and its version is 0 or 1, infer sequential
-                                matches!(latest.version, 0 | 1).then_some(latest.version + 1)
-                            }
-                            _ => unreachable!(),
-                        }
-                    })
-                    .map_or_else(next_timestamp, fmt_sequential)
+                let mut versions = migrator.migrations.iter().rev().map(|m| m.version);
+
+                match (versions.next(), versions.next()) {
+                    (Some(latest), previous)
+                        if VersioningScheme::infer(previous, latest)
+                            == VersioningScheme::Sequential =>
+                    {
+                        fmt_sequential(latest + 1)
+                    }
+                    _ => next_timestamp(),
+                }
             }
             (true, true, _) => unreachable!("BUG: Clap should have rejected this case"),
         }
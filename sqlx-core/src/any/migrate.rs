@@ -1,7 +1,9 @@
 use crate::any::driver;
 use crate::any::{Any, AnyConnection};
 use crate::error::Error;
-use crate::migrate::{AppliedMigration, Migrate, MigrateDatabase, MigrateError, Migration};
+use crate::migrate::{
+    AppliedMigration, InstalledOnType, Migrate, MigrateDatabase, MigrateError, Migration,
+};
 use futures_core::future::BoxFuture;
 use std::time::Duration;
 
@@ -58,6 +60,30 @@ impl Migrate for AnyConnection {
         })
     }
 
+    fn ensure_migrations_table_with<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        installed_on_type: InstalledOnType,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            self.get_migrate()?
+                .ensure_migrations_table_with(table_name, installed_on_type)
+                .await
+        })
+    }
+
+    fn installed_on_type_mismatch<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        installed_on_type: InstalledOnType,
+    ) -> BoxFuture<'e, Result<Option<String>, MigrateError>> {
+        Box::pin(async move {
+            self.get_migrate()?
+                .installed_on_type_mismatch(table_name, installed_on_type)
+                .await
+        })
+    }
+
     fn dirty_version<'e>(
         &'e mut self,
         table_name: &'e str,
@@ -92,6 +118,19 @@ impl Migrate for AnyConnection {
         Box::pin(async { self.get_migrate()?.apply(table_name, migration).await })
     }
 
+    fn apply_with<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+        installed_on_type: InstalledOnType,
+    ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
+        Box::pin(async move {
+            self.get_migrate()?
+                .apply_with(table_name, migration, installed_on_type)
+                .await
+        })
+    }
+
     fn revert<'e>(
         &'e mut self,
         table_name: &'e str,
@@ -0,0 +1,160 @@
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+
+/// A collection of [`Diagnostic`]s produced while validating configuration
+/// or resolving and running migrations.
+///
+/// Warnings from different sources (conflicting environment variables, config lints,
+/// suspicious migration files, etc.) are pushed into the same collection so they can be
+/// rendered consistently, or used to fail a CI job with [`Self::has_errors()`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Diagnostics {
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// A single warning or error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// Where the problem originates, if known.
+    pub location: Option<Location>,
+    /// Human-readable description of the problem.
+    pub message: String,
+}
+
+/// The severity of a [`Diagnostic`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Likely a mistake, but SQLx can continue.
+    Warning,
+    /// SQLx cannot or should not continue.
+    Error,
+}
+
+/// The source of a [`Diagnostic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Location {
+    /// A key in `sqlx.toml`, e.g. `migrate.table-name`.
+    ConfigKey(Cow<'static, str>),
+    /// An environment variable, e.g. `SQLX_MIGRATIONS_TABLE`.
+    EnvVar(Cow<'static, str>),
+    /// A file on disk, e.g. a migration.
+    File(PathBuf),
+}
+
+impl Diagnostics {
+    /// Create an empty collection.
+    pub const fn new() -> Self {
+        Self {
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Add a diagnostic to the collection.
+    pub fn push(&mut self, diagnostic: Diagnostic) -> &mut Self {
+        self.diagnostics.push(diagnostic);
+        self
+    }
+
+    /// Add a [`Severity::Warning`] to the collection.
+    pub fn warn(&mut self, location: Option<Location>, message: impl Into<String>) -> &mut Self {
+        self.push(Diagnostic {
+            severity: Severity::Warning,
+            location,
+            message: message.into(),
+        })
+    }
+
+    /// Add a [`Severity::Error`] to the collection.
+    pub fn error(&mut self, location: Option<Location>, message: impl Into<String>) -> &mut Self {
+        self.push(Diagnostic {
+            severity: Severity::Error,
+            location,
+            message: message.into(),
+        })
+    }
+
+    /// Move all diagnostics from `other` into this collection.
+    pub fn extend(&mut self, other: Diagnostics) -> &mut Self {
+        self.diagnostics.extend(other.diagnostics);
+        self
+    }
+
+    /// Returns `true` if any diagnostic has [`Severity::Error`].
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+
+    /// The highest severity of any diagnostic in the collection, or `None` if it is empty.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.diagnostics.iter().map(|d| d.severity).max()
+    }
+
+    /// Returns `true` if the collection contains no diagnostics.
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// The number of diagnostics in the collection.
+    pub fn len(&self) -> usize {
+        self.diagnostics.len()
+    }
+
+    /// Iterate over the diagnostics in the order they were pushed.
+    pub fn iter(&self) -> std::slice::Iter<'_, Diagnostic> {
+        self.diagnostics.iter()
+    }
+}
+
+impl IntoIterator for Diagnostics {
+    type Item = Diagnostic;
+    type IntoIter = std::vec::IntoIter<Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.diagnostics.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Diagnostics {
+    type Item = &'a Diagnostic;
+    type IntoIter = std::slice::Iter<'a, Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ConfigKey(key) => write!(f, "`{key}` in sqlx.toml"),
+            Self::EnvVar(var) => write!(f, "environment variable `{var}`"),
+            Self::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)?;
+
+        if let Some(location) = &self.location {
+            write!(f, " (at {location})")?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,5 +1,8 @@
 use std::collections::BTreeSet;
 
+#[cfg(feature = "migrate")]
+use crate::config::diagnostics::{Diagnostics, Location};
+
 /// Configuration for migrations when executed using `sqlx::migrate!()` or through `sqlx-cli`.
 ///
 /// ### Note
@@ -33,6 +36,48 @@ pub struct Config {
     /// ```
     pub create_schemas: BTreeSet<Box<str>>,
 
+    /// Specify the order in which the schemas in `create_schemas` are created.
+    ///
+    /// By default, schemas are created in alphabetical order. If the creation of one schema
+    /// depends on another already existing (e.g. an event trigger which references it),
+    /// list them here in the order they should be created.
+    ///
+    /// An entry may also be a list of schemas which don't depend on each other.
+    /// These are created after all previous entries, and may be created concurrently
+    /// if `parallel_schema_creation` is enabled. See [`SchemaOrderEntry`] for details.
+    ///
+    /// If set, this must name every schema in `create_schemas` exactly once,
+    /// and no schemas which are not in `create_schemas`.
+    ///
+    /// ### Example
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// create-schemas = ["audit", "app", "billing"]
+    /// # `app` and `billing` reference `audit` so they must be created after it.
+    /// schema-order = ["audit", ["app", "billing"]]
+    /// ```
+    pub schema_order: Option<Vec<SchemaOrderEntry>>,
+
+    /// Create independent schemas in `create_schemas` concurrently, using multiple connections.
+    ///
+    /// Schemas are independent if they are in the same group in `schema_order`,
+    /// or if `schema_order` is not set. Groups are still created in order.
+    ///
+    /// This can speed up bootstrapping a fresh database with many schemas.
+    ///
+    /// ### Note: Only Supported by `sqlx-cli`
+    /// Only `sqlx migrate run` with PostgreSQL or MySQL creates schemas concurrently.
+    /// `sqlx::migrate!()` runs on a single connection, so always creates schemas one at a time.
+    ///
+    /// ### Example
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// parallel-schema-creation = true
+    /// ```
+    pub parallel_schema_creation: bool,
+
     /// Override the name of the table used to track executed migrations.
     ///
     /// May be schema-qualified and/or contain quotes. Defaults to `_sqlx_migrations`.
@@ -62,6 +107,52 @@ pub struct Config {
     /// Relative to the crate root for `sqlx::migrate!()`, or the current directory for `sqlx-cli`.
     pub migrations_dir: Option<Box<str>>,
 
+    /// Look for migrations in subdirectories of `migrations_dir` as well.
+    ///
+    /// Migrations from all subdirectories are combined into a single set ordered by version,
+    /// so they may be organized however is convenient. Two migrations with the same version
+    /// in different subdirectories is an error.
+    ///
+    /// Files and directories which are not migrations are ignored. SQL files without an integer
+    /// version prefix (e.g. in a `seeds/` subdirectory) are skipped with a warning.
+    ///
+    /// ### Example: Group Migrations by Year
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// # Resolves both `migrations/2024/*.sql` and `migrations/2025/*.sql`
+    /// recursive = true
+    /// ```
+    pub recursive: bool,
+
+    /// Check that all migrations use the same versioning scheme.
+    ///
+    /// A migrations directory mixing timestamp versions (e.g. `20250101120000`) with
+    /// sequential versions (e.g. `0001`) is usually a mistake, and the resulting order
+    /// of migrations may not be what was intended.
+    ///
+    /// Defaults to `allow`. See [`VersioningConsistency`] for details.
+    ///
+    /// ### Example
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// versioning-consistency = "error"
+    /// ```
+    pub versioning_consistency: VersioningConsistency,
+
+    /// Specify what to do with migration files that are not valid UTF-8.
+    ///
+    /// Defaults to `error`. See [`NonUtf8Policy`] for details.
+    ///
+    /// ### Example: Skip Files With Invalid Encoding
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// non-utf8-policy = "skip"
+    /// ```
+    pub non_utf8_policy: NonUtf8Policy,
+
     /// Specify characters that should be ignored when hashing migrations.
     ///
     /// Any characters contained in the given array will be dropped when a migration is hashed.
@@ -114,6 +205,25 @@ pub struct Config {
     // Likely lower overhead for small sets than `HashSet`.
     pub ignored_chars: BTreeSet<char>,
 
+    /// Specify the type of the `installed_on` column of the migrations table,
+    /// and whether the migrator writes it explicitly.
+    ///
+    /// Defaults to `timestamp_tz`. See [`InstalledOnType`] for details.
+    ///
+    /// ### Warning: Only Takes Effect When the Table is Created
+    /// The migrations table is only created if it does not exist, so changing this option
+    /// does not alter the column of an existing table. If you change it, you must migrate
+    /// the column yourself, taking care to convert existing values to the new representation.
+    /// Otherwise, `installed_on` values will be inconsistent or misinterpreted.
+    ///
+    /// ### Example
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// installed-on-type = "timestamp_utc"
+    /// ```
+    pub installed_on_type: InstalledOnType,
+
     /// Specify default options for new migrations created with `sqlx migrate add`.
     pub defaults: MigrationDefaults,
 
@@ -180,7 +290,7 @@ pub enum DefaultVersioning {
     /// Infer the versioning scheme from existing migrations:
     ///
     /// * If the versions of the last two migrations differ by `1`, infer `Sequential`.
-    /// * If only one migration exists and has version `1`, infer `Sequential`.
+    /// * If only one migration exists and has version `0` or `1`, infer `Sequential`.
     /// * Otherwise, infer `Timestamp`.
     #[default]
     Inferred,
@@ -197,6 +307,79 @@ pub enum DefaultVersioning {
     Sequential,
 }
 
+/// The type of the `installed_on` column of the migrations table.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum InstalledOnType {
+    /// Use a timezone-aware column, set by the database to the current time.
+    ///
+    /// `TIMESTAMPTZ` for PostgreSQL; `TIMESTAMP` for MySQL, which stores values in UTC;
+    /// `TIMESTAMP` for SQLite, where `CURRENT_TIMESTAMP` is always UTC.
+    #[default]
+    TimestampTz,
+
+    /// Use a timezone-naive column, set by the database to the current time.
+    ///
+    /// `TIMESTAMP` for PostgreSQL, `DATETIME` for MySQL, `TIMESTAMP` for SQLite.
+    ///
+    /// The value depends on the time zone of the database session.
+    Timestamp,
+
+    /// Use a timezone-naive column, but have the migrator explicitly write the current time in UTC.
+    ///
+    /// Uses the same column types as `Timestamp`, but the value does not depend on the time zone
+    /// of the database session. Useful if migrations are applied from multiple regions.
+    TimestampUtc,
+}
+
+/// What to do if resolved migrations mix timestamp and sequential versions.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum VersioningConsistency {
+    /// Don't check versioning schemes.
+    #[default]
+    Allow,
+
+    /// Emit a warning if both schemes are used.
+    ///
+    /// `sqlx-cli` prints the warning, `migrate!()` emits it as a compiler warning,
+    /// and `Migrator::new()` logs it and makes it available from `Migrator::diagnostics()`.
+    Warn,
+
+    /// Fail to resolve migrations if both schemes are used.
+    Error,
+}
+
+/// What to do with migration files that are not valid UTF-8.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum NonUtf8Policy {
+    /// Fail to resolve migrations, naming the offending file.
+    #[default]
+    Error,
+
+    /// Skip the file with a warning.
+    ///
+    /// The warning is reported the same way as for [`VersioningConsistency::Warn`].
+    ///
+    /// ### Warning
+    /// A skipped migration will not be applied. If it was previously applied,
+    /// this will be reported as a missing migration.
+    Skip,
+}
+
 /// Database-specific migration configuration.
 #[derive(Debug, Default)]
 #[cfg_attr(
@@ -230,6 +413,45 @@ pub struct Postgres {
     pub schema: Option<Box<str>>,
 }
 
+/// An entry in [`Config::schema_order`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "sqlx-toml", derive(serde::Deserialize), serde(untagged))]
+pub enum SchemaOrderEntry {
+    /// A single schema, created after all previous entries.
+    Schema(Box<str>),
+
+    /// Schemas which don't depend on each other, created after all previous entries.
+    ///
+    /// May be created concurrently if [`Config::parallel_schema_creation`] is enabled.
+    Group(Vec<Box<str>>),
+}
+
+impl SchemaOrderEntry {
+    /// The schemas in this entry.
+    pub fn schemas(&self) -> &[Box<str>] {
+        match self {
+            Self::Schema(schema_name) => std::slice::from_ref(schema_name),
+            Self::Group(schema_names) => schema_names,
+        }
+    }
+}
+
+/// Error returned by [`Config::create_schemas_ordered()`] if `schema_order` is invalid.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum SchemaOrderError {
+    /// A schema in `create_schemas` was not listed in `schema_order`.
+    #[error("schema {0:?} is listed in `create-schemas` but missing from `schema-order`")]
+    Missing(Box<str>),
+
+    /// A schema in `schema_order` was not listed in `create_schemas`.
+    #[error("schema {0:?} is listed in `schema-order` but not in `create-schemas`")]
+    Unknown(Box<str>),
+
+    /// A schema was listed more than once in `schema_order`.
+    #[error("schema {0:?} is listed more than once in `schema-order`")]
+    Duplicate(Box<str>),
+}
+
 impl Default for Postgres {
     fn default() -> Self {
         Self {
@@ -239,14 +461,42 @@ impl Default for Postgres {
 }
 
 impl Default for Config {
+    /// Built-in defaults, with `table_name` and `drivers.postgres.schema` read from the
+    /// `SQLX_MIGRATIONS_TABLE` and `SQLX_MIGRATIONS_SCHEMA` environment variables, respectively.
+    ///
+    /// See [`Self::default_without_env()`] to ignore the environment.
     fn default() -> Self {
         Self {
-            create_schemas: Default::default(),
             table_name: std::env::var("SQLX_MIGRATIONS_TABLE").ok().map(Into::into),
+            drivers: Drivers {
+                postgres: Postgres::default(),
+            },
+            ..Self::default_without_env()
+        }
+    }
+}
+
+impl Config {
+    /// Built-in defaults, ignoring the `SQLX_MIGRATIONS_TABLE` and `SQLX_MIGRATIONS_SCHEMA`
+    /// environment variables.
+    ///
+    /// Unlike [`Self::default()`], the result does not depend on the process environment.
+    pub fn default_without_env() -> Self {
+        Self {
+            create_schemas: Default::default(),
+            schema_order: Default::default(),
+            parallel_schema_creation: Default::default(),
+            table_name: None,
             migrations_dir: Default::default(),
+            recursive: Default::default(),
+            versioning_consistency: Default::default(),
+            non_utf8_policy: Default::default(),
             ignored_chars: Default::default(),
+            installed_on_type: Default::default(),
             defaults: Default::default(),
-            drivers: Default::default(),
+            drivers: Drivers {
+                postgres: Postgres { schema: None },
+            },
         }
     }
 }
@@ -278,18 +528,162 @@ impl Config {
 
     /// Get the schema name for PostgreSQL migrations.
     /// Returns None for other databases.
+    ///
+    /// `SQLX_MIGRATIONS_SCHEMA` is read when the config is constructed or loaded,
+    /// not by this method.
     pub fn postgres_schema(&self) -> Option<String> {
         self.drivers
             .postgres
             .schema
             .as_deref()
             .map(|s| s.to_string())
-            .or_else(|| std::env::var("SQLX_MIGRATIONS_SCHEMA").ok())
+    }
+
+    /// Get the schemas in `create_schemas` in the order they should be created.
+    ///
+    /// Follows `schema_order` if set, otherwise alphabetical order.
+    ///
+    /// # Errors
+    /// If `schema_order` is set but does not list every schema in `create_schemas` exactly once,
+    /// or lists a schema that is not in `create_schemas`.
+    pub fn create_schemas_ordered(&self) -> Result<Vec<&str>, SchemaOrderError> {
+        Ok(self.create_schemas_levels()?.concat())
+    }
+
+    /// Get the schemas in `create_schemas` grouped into levels, where each schema
+    /// may depend on schemas in previous levels but not on those in its own level.
+    ///
+    /// Follows `schema_order` if set. Otherwise, all schemas are in a single level
+    /// in alphabetical order.
+    ///
+    /// # Errors
+    /// Same as [`Self::create_schemas_ordered()`].
+    pub fn create_schemas_levels(&self) -> Result<Vec<Vec<&str>>, SchemaOrderError> {
+        let Some(schema_order) = &self.schema_order else {
+            if self.create_schemas.is_empty() {
+                return Ok(vec![]);
+            }
+
+            return Ok(vec![self.create_schemas.iter().map(|s| &**s).collect()]);
+        };
+
+        let mut seen = BTreeSet::new();
+
+        for schema_name in schema_order.iter().flat_map(SchemaOrderEntry::schemas) {
+            if !self.create_schemas.contains(schema_name) {
+                return Err(SchemaOrderError::Unknown(schema_name.clone()));
+            }
+
+            if !seen.insert(&**schema_name) {
+                return Err(SchemaOrderError::Duplicate(schema_name.clone()));
+            }
+        }
+
+        if let Some(missing) = self.create_schemas.iter().find(|s| !seen.contains(&***s)) {
+            return Err(SchemaOrderError::Missing(missing.clone()));
+        }
+
+        Ok(schema_order
+            .iter()
+            .map(|entry| entry.schemas().iter().map(|s| &**s).collect())
+            .collect())
+    }
+
+    /// Get the schemas in `create_schemas` grouped into batches, in order, where the schemas
+    /// in each batch may be created concurrently.
+    ///
+    /// If `parallel_schema_creation` is enabled, each level from [`Self::create_schemas_levels()`]
+    /// is split into batches of at most `max_concurrency` schemas.
+    /// Otherwise, each batch contains exactly one schema.
+    ///
+    /// # Errors
+    /// Same as [`Self::create_schemas_ordered()`].
+    pub fn create_schemas_batches(
+        &self,
+        max_concurrency: usize,
+    ) -> Result<Vec<Vec<&str>>, SchemaOrderError> {
+        let levels = self.create_schemas_levels()?;
+
+        let batch_size = if self.parallel_schema_creation {
+            std::cmp::max(max_concurrency, 1)
+        } else {
+            1
+        };
+
+        Ok(levels
+            .iter()
+            .flat_map(|level| level.chunks(batch_size).map(<[&str]>::to_vec))
+            .collect())
+    }
+
+    /// Check this configuration for likely mistakes, pushing any found into `diagnostics`.
+    ///
+    /// Includes [`Self::check_env_conflicts()`]. Called by [`crate::config::Config::validate()`].
+    pub fn push_diagnostics(&self, diagnostics: &mut Diagnostics) {
+        self.check_env_conflicts(diagnostics);
+
+        if let Err(e) = self.create_schemas_ordered() {
+            diagnostics.error(
+                Some(Location::ConfigKey("migrate.schema-order".into())),
+                e.to_string(),
+            );
+        }
+
+        if let (Some(table_name), Some(schema)) = (&self.table_name, self.postgres_schema()) {
+            if table_name.contains('.') {
+                diagnostics.warn(
+                    Some(Location::ConfigKey("migrate.table-name".into())),
+                    format!(
+                        "table name {table_name:?} is already schema-qualified but a Postgres schema \
+                         ({schema:?}) is also set; the resulting table name will be {:?}",
+                        self.table_name()
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Warn if a value set in `sqlx.toml` disagrees with the environment variable
+    /// that would otherwise provide it.
+    ///
+    /// The config file takes precedence, but the conflict usually indicates a mistake.
+    pub fn check_env_conflicts(&self, diagnostics: &mut Diagnostics) {
+        let conflicts = [
+            (
+                "SQLX_MIGRATIONS_TABLE",
+                "migrate.table-name",
+                self.table_name.as_deref(),
+            ),
+            (
+                "SQLX_MIGRATIONS_SCHEMA",
+                "migrate.drivers.postgres.schema",
+                self.drivers.postgres.schema.as_deref(),
+            ),
+        ];
+
+        for (var, key, configured) in conflicts {
+            let (Ok(env), Some(configured)) = (std::env::var(var), configured) else {
+                continue;
+            };
+
+            if env != configured {
+                diagnostics.warn(
+                    Some(Location::EnvVar(var.into())),
+                    format!(
+                        "`{var}` is set to {env:?} but `{key}` is set to {configured:?}; \
+                         using the value from sqlx.toml"
+                    ),
+                );
+            }
+        }
     }
 
     pub fn to_resolve_config(&self) -> crate::migrate::ResolveConfig {
         let mut config = crate::migrate::ResolveConfig::new();
         config.ignore_chars(self.ignored_chars.iter().copied());
+        config.set_recursive(self.recursive);
+        config.set_versioning_consistency(self.versioning_consistency);
+        config.set_non_utf8_policy(self.non_utf8_policy);
         config
     }
 }
@@ -21,6 +21,12 @@ use std::path::{Path, PathBuf};
 /// See [`common::Config`] for details.
 pub mod common;
 
+/// Structured warnings and errors collected while validating configuration
+/// and resolving migrations.
+///
+/// See [`diagnostics::Diagnostics`] for details.
+pub mod diagnostics;
+
 pub mod drivers;
 
 /// Configuration for the `query!()` family of macros.
@@ -148,6 +154,23 @@ impl ConfigError {
     }
 }
 
+impl Config {
+    /// Check this configuration for likely mistakes.
+    ///
+    /// Returns all warnings and errors found, which may be empty.
+    /// This does not include diagnostics from resolving migrations,
+    /// which are reported separately by the migration loader.
+    pub fn validate(&self) -> diagnostics::Diagnostics {
+        #[allow(unused_mut)]
+        let mut diagnostics = diagnostics::Diagnostics::new();
+
+        #[cfg(feature = "migrate")]
+        self.migrate.push_diagnostics(&mut diagnostics);
+
+        diagnostics
+    }
+}
+
 /// Internal methods for loading a `Config`.
 #[allow(clippy::result_large_err)]
 impl Config {
@@ -109,11 +109,16 @@ fn assert_migrate_config(config: &config::migrate::Config) {
 
     assert_eq!(config.table_name.as_deref(), Some("foo._sqlx_migrations"));
     assert_eq!(config.migrations_dir.as_deref(), Some("foo/migrations"));
+    assert!(config.recursive);
+    assert_eq!(config.versioning_consistency, VersioningConsistency::Warn);
+    assert_eq!(config.non_utf8_policy, NonUtf8Policy::Skip);
 
     let ignored_chars = BTreeSet::from([' ', '\t', '\r', '\n', '\u{FEFF}']);
 
     assert_eq!(config.ignored_chars, ignored_chars);
 
+    assert_eq!(config.installed_on_type, InstalledOnType::TimestampUtc);
+
     assert_eq!(
         config.defaults.migration_type,
         DefaultMigrationType::Reversible
@@ -147,19 +152,156 @@ fn test_migrate_env_var_support() {
 fn test_migrate_defaults_without_env() {
     use config::migrate::Config;
 
-    // This test verifies behavior when env vars are NOT set
-    // We test this by creating the config with explicit None values
-    let config = Config {
-        create_schemas: Default::default(),
-        table_name: None,
-        migrations_dir: Default::default(),
-        ignored_chars: Default::default(),
-        defaults: Default::default(),
-        drivers: config::migrate::Drivers {
-            postgres: config::migrate::Postgres { schema: None },
-        },
-    };
+    // Ensure the environment variables are set so we know they're being ignored.
+    init_test_env();
+
+    let config = Config::default_without_env();
+
+    assert_eq!(config.table_name, None);
+    assert_eq!(config.drivers.postgres.schema, None);
 
     assert_eq!(config.table_name(), "_sqlx_migrations");
     assert_eq!(config.postgres_schema(), None);
 }
+
+#[test]
+fn test_migrate_create_schemas_default_order() {
+    let config: config::migrate::Config = toml::from_str(
+        r#"
+        create-schemas = ["foo", "bar", "baz"]
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.create_schemas_ordered().unwrap(),
+        ["bar", "baz", "foo"]
+    );
+}
+
+#[test]
+fn test_migrate_schema_order() {
+    let config: config::migrate::Config = toml::from_str(
+        r#"
+        create-schemas = ["foo", "bar", "baz"]
+        schema-order = ["foo", "baz", "bar"]
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.create_schemas_ordered().unwrap(),
+        ["foo", "baz", "bar"]
+    );
+}
+
+#[test]
+fn test_migrate_schema_order_missing_schema() {
+    use config::migrate::SchemaOrderError;
+
+    let config: config::migrate::Config = toml::from_str(
+        r#"
+        create-schemas = ["foo", "bar", "baz"]
+        schema-order = ["foo", "bar"]
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.create_schemas_ordered(),
+        Err(SchemaOrderError::Missing("baz".into()))
+    );
+}
+
+#[test]
+fn test_validate_collects_diagnostics() {
+    use config::diagnostics::{Location, Severity};
+
+    init_test_env();
+
+    // `table-name` conflicts with `SQLX_MIGRATIONS_TABLE`, and is schema-qualified
+    // while `SQLX_MIGRATIONS_SCHEMA` also provides a schema.
+    let config: Config = toml::from_str(
+        r#"
+        [migrate]
+        table-name = "foo._sqlx_migrations"
+        "#,
+    )
+    .unwrap();
+
+    let diagnostics = config.validate();
+
+    assert_eq!(diagnostics.len(), 2, "{diagnostics:?}");
+    assert!(!diagnostics.has_errors());
+
+    let locations: Vec<_> = diagnostics
+        .iter()
+        .map(|d| {
+            assert_eq!(d.severity, Severity::Warning);
+            d.location.clone()
+        })
+        .collect();
+
+    assert_eq!(
+        locations,
+        [
+            Some(Location::EnvVar("SQLX_MIGRATIONS_TABLE".into())),
+            Some(Location::ConfigKey("migrate.table-name".into())),
+        ]
+    );
+}
+
+#[test]
+fn test_migrate_parallel_schema_creation() {
+    let config: config::migrate::Config = toml::from_str(
+        r#"
+        create-schemas = ["analytics", "app", "audit", "billing", "reports"]
+        # `app`, `billing` and `reports` depend on `audit` but not each other,
+        # and `analytics` depends on all of them.
+        schema-order = ["audit", ["app", "billing", "reports"], "analytics"]
+        parallel-schema-creation = true
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.create_schemas_levels().unwrap(),
+        [
+            vec!["audit"],
+            vec!["app", "billing", "reports"],
+            vec!["analytics"]
+        ]
+    );
+
+    // Independent schemas are batched, but never with schemas they depend on.
+    assert_eq!(
+        config.create_schemas_batches(2).unwrap(),
+        [
+            vec!["audit"],
+            vec!["app", "billing"],
+            vec!["reports"],
+            vec!["analytics"]
+        ]
+    );
+
+    assert_eq!(
+        config.create_schemas_ordered().unwrap(),
+        ["audit", "app", "billing", "reports", "analytics"]
+    );
+}
+
+#[test]
+fn test_migrate_serial_schema_creation() {
+    let config: config::migrate::Config = toml::from_str(
+        r#"
+        create-schemas = ["app", "audit", "billing"]
+        schema-order = ["audit", ["app", "billing"]]
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.create_schemas_batches(4).unwrap(),
+        [vec!["audit"], vec!["app"], vec!["billing"]]
+    );
+}
@@ -1,5 +1,5 @@
 use crate::error::Error;
-use crate::migrate::{AppliedMigration, MigrateError, Migration};
+use crate::migrate::{AppliedMigration, InstalledOnType, MigrateError, Migration};
 use futures_core::future::BoxFuture;
 use std::future::Future;
 use std::time::Duration;
@@ -39,6 +39,30 @@ pub trait Migrate {
         table_name: &'e str,
     ) -> BoxFuture<'e, Result<(), MigrateError>>;
 
+    /// Like [`Self::ensure_migrations_table()`], but creates the `installed_on` column
+    /// with the given type if the table does not exist yet.
+    ///
+    /// The default implementation ignores `installed_on_type`.
+    fn ensure_migrations_table_with<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        _installed_on_type: InstalledOnType,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        self.ensure_migrations_table(table_name)
+    }
+
+    /// Return the type of the `installed_on` column of an existing migrations table
+    /// if it is not the type that `installed_on_type` would create, or `None` otherwise.
+    ///
+    /// The default implementation always returns `None`.
+    fn installed_on_type_mismatch<'e>(
+        &'e mut self,
+        _table_name: &'e str,
+        _installed_on_type: InstalledOnType,
+    ) -> BoxFuture<'e, Result<Option<String>, MigrateError>> {
+        Box::pin(async { Ok(None) })
+    }
+
     // Return the version on which the database is dirty or None otherwise.
     // "dirty" means there is a partially applied migration that failed.
     fn dirty_version<'e>(
@@ -70,6 +94,19 @@ pub trait Migrate {
         migration: &'e Migration,
     ) -> BoxFuture<'e, Result<Duration, MigrateError>>;
 
+    /// Like [`Self::apply()`], but records the migration according to `installed_on_type`,
+    /// e.g. writing `installed_on` explicitly for [`InstalledOnType::TimestampUtc`].
+    ///
+    /// The default implementation ignores `installed_on_type`.
+    fn apply_with<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+        _installed_on_type: InstalledOnType,
+    ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
+        self.apply(table_name, migration)
+    }
+
     // run a revert SQL from migration in a DDL transaction
     // deletes the row in [_migrations] table with specified migration version on completion (success or failure)
     // returns the time taking to run the migration SQL
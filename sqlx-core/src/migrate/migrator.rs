@@ -1,7 +1,12 @@
 use crate::acquire::Acquire;
-use crate::migrate::{AppliedMigration, Migrate, MigrateError, Migration, MigrationSource};
+use crate::config::diagnostics::{Diagnostics, Severity};
+use crate::migrate::{
+    check_installed_on_type, AppliedMigration, DatabaseKind, InstalledOnType, Migrate,
+    MigrateError, Migration, MigrationSource,
+};
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
 use std::ops::Deref;
 use std::slice;
 
@@ -28,6 +33,13 @@ pub struct Migrator {
 
     #[doc(hidden)]
     pub create_schemas: Cow<'static, [Cow<'static, str>]>,
+
+    #[doc(hidden)]
+    pub installed_on_type: InstalledOnType,
+
+    // `None` instead of empty so `migrate!()` stays const-promotable.
+    #[doc(hidden)]
+    pub diagnostics: Option<Diagnostics>,
 }
 
 impl Migrator {
@@ -39,6 +51,8 @@ impl Migrator {
         locking: true,
         table_name: Cow::Borrowed("_sqlx_migrations"),
         create_schemas: Cow::Borrowed(&[]),
+        installed_on_type: InstalledOnType::TimestampTz,
+        diagnostics: None,
     };
 
     /// Creates a new instance with the given source.
@@ -59,12 +73,23 @@ impl Migrator {
     /// # }
     /// ```
     /// See [MigrationSource] for details on structure of the `./migrations` directory.
+    ///
+    /// Any warnings produced while resolving migrations are logged,
+    /// and available from [`Self::diagnostics()`].
     pub async fn new<'s, S>(source: S) -> Result<Self, MigrateError>
     where
         S: MigrationSource<'s>,
     {
+        let (migrations, diagnostics) = source
+            .resolve_with_diagnostics()
+            .await
+            .map_err(MigrateError::Source)?;
+
+        log_diagnostics(&diagnostics);
+
         Ok(Self {
-            migrations: Cow::Owned(source.resolve().await.map_err(MigrateError::Source)?),
+            migrations: Cow::Owned(migrations),
+            diagnostics: Some(diagnostics),
             ..Self::DEFAULT
         })
     }
@@ -127,6 +152,17 @@ impl Migrator {
         self
     }
 
+    /// Set the type of the `installed_on` column of the migrations table.
+    ///
+    /// See [`InstalledOnType`] for details.
+    ///
+    /// ### Note: Only Takes Effect When the Table is Created
+    /// Changing this does not alter the column of an existing migrations table.
+    pub fn set_installed_on_type(&mut self, installed_on_type: InstalledOnType) -> &mut Self {
+        self.installed_on_type = installed_on_type;
+        self
+    }
+
     /// Specify whether applied migrations that are missing from the resolved migrations should be ignored.
     pub fn set_ignore_missing(&mut self, ignore_missing: bool) -> &mut Self {
         self.ignore_missing = ignore_missing;
@@ -156,6 +192,99 @@ impl Migrator {
         self.iter().any(|m| m.version == version)
     }
 
+    /// Get the warnings produced while resolving migrations, e.g. for files that were skipped.
+    ///
+    /// Always empty if the migrations were not resolved by [`Self::new()`].
+    /// `migrate!()` emits these warnings at compile time instead.
+    pub fn diagnostics(&self) -> &Diagnostics {
+        static EMPTY: Diagnostics = Diagnostics::new();
+
+        self.diagnostics.as_ref().unwrap_or(&EMPTY)
+    }
+
+    /// Render the SQL that [`Self::run()`] would execute as a standalone script,
+    /// for review and manual application without connecting to the database.
+    ///
+    /// The script creates the configured schemas and the migrations table if they don't exist,
+    /// then applies each migration whose version is not in `applied` (empty for a fresh database)
+    /// and records it in the migrations table. Each migration is wrapped in a transaction
+    /// unless it opted out with `-- no-transaction`.
+    ///
+    /// Unlike [`Self::run()`], the script does not lock the database or check the checksums
+    /// of previously applied migrations.
+    ///
+    /// ### Note: SQLite
+    /// SQLite cannot create schemas in a single statement, so any schemas are only noted
+    /// in comments and must be attached before running the script.
+    pub fn render_offline_script(&self, kind: DatabaseKind, applied: &[i64]) -> String {
+        let applied: HashSet<i64> = applied.iter().copied().collect();
+
+        let mut script = String::new();
+
+        // `write!()` to a `String` cannot fail.
+        let _ = writeln!(script, "-- Migration script generated by SQLx for {kind:?}");
+
+        for schema_name in self.create_schemas.iter().filter(|name| !name.is_empty()) {
+            let _ = match kind.create_schema_sql(schema_name) {
+                Some(sql) => writeln!(script, "\n{sql}"),
+                None => writeln!(
+                    script,
+                    "\n-- Schema {schema_name} must be attached before running this script"
+                ),
+            };
+        }
+
+        let _ = writeln!(
+            script,
+            "\n{}",
+            kind.create_migrations_table_sql(&self.table_name, self.installed_on_type)
+                .trim()
+        );
+
+        for migration in self.iter() {
+            if migration.migration_type.is_down_migration() || applied.contains(&migration.version)
+            {
+                continue;
+            }
+
+            let _ = writeln!(
+                script,
+                "\n-- {}/{} {}",
+                migration.version,
+                migration.migration_type.label(),
+                migration.description
+            );
+
+            if !migration.no_tx {
+                let _ = writeln!(script, "{}", kind.begin_sql());
+            }
+
+            let sql = migration.sql.as_str().trim();
+            let _ = writeln!(script, "{sql}");
+
+            // On its own line, in case the migration ends with a `--` comment.
+            if !sql.ends_with(';') {
+                let _ = writeln!(script, ";");
+            }
+
+            let _ = writeln!(
+                script,
+                "{}",
+                kind.insert_migration_literal_sql(
+                    &self.table_name,
+                    migration,
+                    self.installed_on_type
+                )
+            );
+
+            if !migration.no_tx {
+                let _ = writeln!(script, "COMMIT;");
+            }
+        }
+
+        script
+    }
+
     /// Run any pending migrations against the database; and, validate previously applied migrations
     /// against the current migration source to detect accidental changes in previously-applied migrations.
     ///
@@ -183,6 +312,25 @@ impl Migrator {
         self.run_direct(None, &mut *conn).await
     }
 
+    /// Like [`Self::run()`], but pushes any warnings into `diagnostics` instead of logging them.
+    ///
+    /// The warnings from resolving migrations (see [`Self::diagnostics()`]) are pushed first,
+    /// so `diagnostics` collects everything reported while loading and running migrations.
+    pub async fn run_with_diagnostics<'a, A>(
+        &self,
+        migrator: A,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<(), MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate,
+    {
+        diagnostics.extend(self.diagnostics().clone());
+
+        let mut conn = migrator.acquire().await?;
+        self.run_inner(None, &mut *conn, diagnostics).await
+    }
+
     pub async fn run_to<'a, A>(&self, target: i64, migrator: A) -> Result<(), MigrateError>
     where
         A: Acquire<'a>,
@@ -195,6 +343,21 @@ impl Migrator {
     // Getting around the annoying "implementation of `Acquire` is not general enough" error
     #[doc(hidden)]
     pub async fn run_direct<C>(&self, target: Option<i64>, conn: &mut C) -> Result<(), MigrateError>
+    where
+        C: Migrate,
+    {
+        let mut diagnostics = Diagnostics::new();
+        let res = self.run_inner(target, conn, &mut diagnostics).await;
+        log_diagnostics(&diagnostics);
+        res
+    }
+
+    async fn run_inner<C>(
+        &self,
+        target: Option<i64>,
+        conn: &mut C,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<(), MigrateError>
     where
         C: Migrate,
     {
@@ -209,7 +372,11 @@ impl Migrator {
 
         // creates [_migrations] table only if needed
         // eventually this will likely migrate previous versions of the table
-        conn.ensure_migrations_table(&self.table_name).await?;
+        conn.ensure_migrations_table_with(&self.table_name, self.installed_on_type)
+            .await?;
+
+        check_installed_on_type(conn, &self.table_name, self.installed_on_type, diagnostics)
+            .await?;
 
         let version = conn.dirty_version(&self.table_name).await?;
         if let Some(version) = version {
@@ -219,6 +386,13 @@ impl Migrator {
         let applied_migrations = conn.list_applied_migrations(&self.table_name).await?;
         validate_applied_migrations(&applied_migrations, self)?;
 
+        check_out_of_order(
+            self.iter()
+                .take_while(|m| !target.is_some_and(|target| target < m.version)),
+            &applied_migrations,
+            diagnostics,
+        );
+
         let applied_migrations: HashMap<_, _> = applied_migrations
             .into_iter()
             .map(|m| (m.version, m))
@@ -241,7 +415,8 @@ impl Migrator {
                     }
                 }
                 None => {
-                    conn.apply(&self.table_name, migration).await?;
+                    conn.apply_with(&self.table_name, migration, self.installed_on_type)
+                        .await?;
                 }
             }
         }
@@ -279,6 +454,40 @@ impl Migrator {
     {
         let mut conn = migrator.acquire().await?;
 
+        let mut diagnostics = Diagnostics::new();
+        let res = self.undo_inner(&mut *conn, target, &mut diagnostics).await;
+        log_diagnostics(&diagnostics);
+        res
+    }
+
+    /// Like [`Self::undo()`], but pushes any warnings into `diagnostics` instead of logging them.
+    ///
+    /// See [`Self::run_with_diagnostics()`] for details.
+    pub async fn undo_with_diagnostics<'a, A>(
+        &self,
+        migrator: A,
+        target: i64,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<(), MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate,
+    {
+        diagnostics.extend(self.diagnostics().clone());
+
+        let mut conn = migrator.acquire().await?;
+        self.undo_inner(&mut *conn, target, diagnostics).await
+    }
+
+    async fn undo_inner<C>(
+        &self,
+        conn: &mut C,
+        target: i64,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<(), MigrateError>
+    where
+        C: Migrate,
+    {
         // lock the database for exclusive access by the migrator
         if self.locking {
             conn.lock().await?;
@@ -286,7 +495,11 @@ impl Migrator {
 
         // creates [_migrations] table only if needed
         // eventually this will likely migrate previous versions of the table
-        conn.ensure_migrations_table(&self.table_name).await?;
+        conn.ensure_migrations_table_with(&self.table_name, self.installed_on_type)
+            .await?;
+
+        check_installed_on_type(conn, &self.table_name, self.installed_on_type, diagnostics)
+            .await?;
 
         let version = conn.dirty_version(&self.table_name).await?;
         if let Some(version) = version {
@@ -321,6 +534,15 @@ impl Migrator {
     }
 }
 
+fn log_diagnostics(diagnostics: &Diagnostics) {
+    for diagnostic in diagnostics {
+        match diagnostic.severity {
+            Severity::Warning => tracing::warn!("{diagnostic}"),
+            Severity::Error => tracing::error!("{diagnostic}"),
+        }
+    }
+}
+
 fn validate_applied_migrations(
     applied_migrations: &[AppliedMigration],
     migrator: &Migrator,
@@ -339,3 +561,183 @@ fn validate_applied_migrations(
 
     Ok(())
 }
+
+/// Warn about each of `migrations` which is not applied yet but is older than
+/// the latest of `applied_migrations`, and so would be applied out of order.
+#[doc(hidden)]
+pub fn check_out_of_order<'m>(
+    migrations: impl IntoIterator<Item = &'m Migration>,
+    applied_migrations: &[AppliedMigration],
+    diagnostics: &mut Diagnostics,
+) {
+    let Some(latest) = applied_migrations.iter().map(|m| m.version).max() else {
+        return;
+    };
+
+    let applied: HashSet<i64> = applied_migrations.iter().map(|m| m.version).collect();
+
+    for migration in migrations {
+        // Most likely merged from another branch after newer migrations were applied.
+        if !migration.migration_type.is_down_migration()
+            && !applied.contains(&migration.version)
+            && migration.version < latest
+        {
+            diagnostics.warn(
+                None,
+                format!(
+                    "migration {} is older than the latest applied migration {latest}; \
+                     applying it out of order",
+                    migration.version
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migrate::MigrationType;
+    use crate::sql_str::SqlSafeStr;
+
+    fn migrator() -> Migrator {
+        let mut migrator = Migrator::with_migrations(vec![
+            Migration::new(
+                1,
+                "create users".into(),
+                MigrationType::ReversibleUp,
+                "CREATE TABLE app.users ( id BIGINT PRIMARY KEY )".into_sql_str(),
+                false,
+            ),
+            Migration::new(
+                1,
+                "create users".into(),
+                MigrationType::ReversibleDown,
+                "DROP TABLE app.users;".into_sql_str(),
+                false,
+            ),
+            Migration::new(
+                2,
+                "user's index".into(),
+                MigrationType::ReversibleUp,
+                "-- no-transaction\nCREATE INDEX CONCURRENTLY ON app.users ( id );".into_sql_str(),
+                true,
+            ),
+        ]);
+
+        migrator.dangerous_set_table_name("app._sqlx_migrations");
+        migrator.create_schema("app");
+        migrator
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn render_offline_script_fresh() {
+        let migrator = migrator();
+        let script = migrator.render_offline_script(DatabaseKind::Postgres, &[]);
+
+        let checksums: Vec<String> = migrator
+            .iter()
+            .filter(|m| !m.migration_type.is_down_migration())
+            .map(|m| hex(&m.checksum))
+            .collect();
+
+        let expected = [
+            "CREATE SCHEMA IF NOT EXISTS app;".to_string(),
+            "CREATE TABLE IF NOT EXISTS app._sqlx_migrations (".to_string(),
+            "installed_on TIMESTAMPTZ NOT NULL DEFAULT now(),".to_string(),
+            format!(
+                "-- 1/migrate create users\n\
+                 BEGIN;\n\
+                 CREATE TABLE app.users ( id BIGINT PRIMARY KEY )\n\
+                 ;\n\
+                 INSERT INTO app._sqlx_migrations ( version, description, success, checksum, execution_time )\n\
+                 VALUES ( 1, 'create users', TRUE, decode('{}', 'hex'), -1 );\n\
+                 COMMIT;\n",
+                checksums[0]
+            ),
+            // No transaction, and the description is escaped.
+            format!(
+                "-- 2/migrate user's index\n\
+                 -- no-transaction\n\
+                 CREATE INDEX CONCURRENTLY ON app.users ( id );\n\
+                 INSERT INTO app._sqlx_migrations ( version, description, success, checksum, execution_time )\n\
+                 VALUES ( 2, 'user''s index', TRUE, decode('{}', 'hex'), -1 );\n",
+                checksums[1]
+            ),
+        ];
+
+        // Each expected statement appears, in order.
+        let mut rest = &script[..];
+        for statement in &expected {
+            let pos = rest
+                .find(&**statement)
+                .unwrap_or_else(|| panic!("expected {statement:?} in script:\n{script}"));
+            rest = &rest[pos + statement.len()..];
+        }
+
+        assert!(!script.contains("DROP TABLE"), "{script}");
+        assert_eq!(script.matches("COMMIT;").count(), 1, "{script}");
+    }
+
+    #[test]
+    fn render_offline_script_skips_applied() {
+        let script = migrator().render_offline_script(DatabaseKind::MySql, &[1]);
+
+        assert!(script.contains("CREATE TABLE IF NOT EXISTS app._sqlx_migrations ("));
+        assert!(!script.contains("CREATE TABLE app.users"), "{script}");
+        assert!(!script.contains("START TRANSACTION;"), "{script}");
+        assert!(
+            script.contains(&format!(
+                "VALUES ( 2, X'{}', TRUE, X'",
+                hex(b"user's index")
+            )),
+            "{script}"
+        );
+    }
+
+    #[test]
+    fn render_offline_script_trailing_comment() {
+        let mut migrator = Migrator::with_migrations(vec![Migration::new(
+            1,
+            "create users".into(),
+            MigrationType::Simple,
+            "CREATE TABLE users ( id BIGINT PRIMARY KEY ) -- no semicolon".into_sql_str(),
+            false,
+        )]);
+        migrator.create_schema("");
+
+        let script = migrator.render_offline_script(DatabaseKind::Postgres, &[]);
+
+        assert!(
+            script.contains("CREATE TABLE users ( id BIGINT PRIMARY KEY ) -- no semicolon\n;\n"),
+            "{script}"
+        );
+        assert!(!script.contains("CREATE SCHEMA"), "{script}");
+    }
+
+    #[test]
+    fn render_offline_script_timestamp_utc() {
+        let mut migrator = migrator();
+        migrator.set_installed_on_type(InstalledOnType::TimestampUtc);
+
+        let script = migrator.render_offline_script(DatabaseKind::Postgres, &[]);
+
+        assert!(
+            script.contains("installed_on TIMESTAMP NOT NULL DEFAULT now(),"),
+            "{script}"
+        );
+        // The time is taken when the script is run, not when it is rendered.
+        assert!(
+            script.contains("VALUES ( 1, 'create users', (now() AT TIME ZONE 'UTC'), TRUE, "),
+            "{script}"
+        );
+        assert!(
+            script.contains("VALUES ( 2, 'user''s index', (now() AT TIME ZONE 'UTC'), TRUE, "),
+            "{script}"
+        );
+    }
+}
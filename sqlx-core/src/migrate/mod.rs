@@ -5,6 +5,8 @@ mod migration;
 mod migration_type;
 mod migrator;
 mod source;
+mod table;
+mod versioning;
 
 pub use error::MigrateError;
 pub use migrate::{Migrate, MigrateDatabase};
@@ -12,6 +14,18 @@ pub use migration::{AppliedMigration, Migration};
 pub use migration_type::MigrationType;
 pub use migrator::Migrator;
 pub use source::{MigrationSource, ResolveConfig, ResolveWith};
+pub use table::DatabaseKind;
+pub use versioning::VersioningScheme;
+
+pub use crate::config::diagnostics::{Diagnostic, Diagnostics, Location, Severity};
+pub use crate::config::migrate::{InstalledOnType, NonUtf8Policy, VersioningConsistency};
+
+#[doc(hidden)]
+pub use migrator::check_out_of_order;
+#[doc(hidden)]
+pub use table::check_installed_on_type;
 
 #[doc(hidden)]
-pub use source::{resolve_blocking, resolve_blocking_with_config};
+pub use source::{
+    resolve_blocking, resolve_blocking_with_config, resolve_blocking_with_diagnostics,
+};
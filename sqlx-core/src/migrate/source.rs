@@ -1,5 +1,7 @@
+use crate::config::diagnostics::{Diagnostics, Location};
+use crate::config::migrate::{NonUtf8Policy, VersioningConsistency};
 use crate::error::BoxDynError;
-use crate::migrate::{migration, Migration, MigrationType};
+use crate::migrate::{migration, Migration, MigrationType, VersioningScheme};
 use crate::sql_str::{AssertSqlSafe, SqlSafeStr};
 use futures_core::future::BoxFuture;
 
@@ -26,6 +28,21 @@ use std::path::{Path, PathBuf};
 /// changes and it has already been run, this will cause an error.
 pub trait MigrationSource<'s>: Debug {
     fn resolve(self) -> BoxFuture<'s, Result<Vec<Migration>, BoxDynError>>;
+
+    /// Like [`Self::resolve()`], but also returns any warnings produced while resolving,
+    /// e.g. for files that were skipped.
+    ///
+    /// The default implementation returns no warnings.
+    fn resolve_with_diagnostics(
+        self,
+    ) -> BoxFuture<'s, Result<(Vec<Migration>, Diagnostics), BoxDynError>>
+    where
+        Self: Sized,
+    {
+        let resolve = self.resolve();
+
+        Box::pin(async move { Ok((resolve.await?, Diagnostics::new())) })
+    }
 }
 
 impl<'s> MigrationSource<'s> for &'s Path {
@@ -34,6 +51,12 @@ impl<'s> MigrationSource<'s> for &'s Path {
         // since it might require going to disk to fetch filesystem data.
         self.to_owned().resolve()
     }
+
+    fn resolve_with_diagnostics(
+        self,
+    ) -> BoxFuture<'s, Result<(Vec<Migration>, Diagnostics), BoxDynError>> {
+        self.to_owned().resolve_with_diagnostics()
+    }
 }
 
 impl MigrationSource<'static> for PathBuf {
@@ -50,6 +73,12 @@ impl MigrationSource<'static> for PathBuf {
             .await
         })
     }
+
+    fn resolve_with_diagnostics(
+        self,
+    ) -> BoxFuture<'static, Result<(Vec<Migration>, Diagnostics), BoxDynError>> {
+        ResolveWith(self, ResolveConfig::new()).resolve_with_diagnostics()
+    }
 }
 
 /// A [`MigrationSource`] implementation with configurable resolution.
@@ -73,6 +102,27 @@ impl<'s, S: Debug + Into<PathBuf> + Send + 's> MigrationSource<'s> for ResolveWi
             Ok(migrations_with_paths.into_iter().map(|(m, _p)| m).collect())
         })
     }
+
+    fn resolve_with_diagnostics(
+        self,
+    ) -> BoxFuture<'s, Result<(Vec<Migration>, Diagnostics), BoxDynError>> {
+        Box::pin(async move {
+            let path = self.0.into();
+            let config = self.1;
+
+            let (migrations_with_paths, diagnostics) = crate::rt::spawn_blocking(move || {
+                let mut diagnostics = Diagnostics::new();
+
+                resolve_blocking_with_diagnostics(&path, &config, &mut diagnostics)
+                    .map(|migrations| (migrations, diagnostics))
+            })
+            .await?;
+
+            let migrations = migrations_with_paths.into_iter().map(|(m, _p)| m).collect();
+
+            Ok((migrations, diagnostics))
+        })
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -87,6 +137,9 @@ pub struct ResolveError {
 #[derive(Debug, Default)]
 pub struct ResolveConfig {
     ignored_chars: BTreeSet<char>,
+    recursive: bool,
+    versioning_consistency: VersioningConsistency,
+    non_utf8_policy: NonUtf8Policy,
 }
 
 impl ResolveConfig {
@@ -94,9 +147,46 @@ impl ResolveConfig {
     pub fn new() -> Self {
         ResolveConfig {
             ignored_chars: BTreeSet::new(),
+            recursive: false,
+            versioning_consistency: VersioningConsistency::Allow,
+            non_utf8_policy: NonUtf8Policy::Error,
         }
     }
 
+    /// Specify whether to look for migrations in subdirectories. Defaults to `false`.
+    ///
+    /// If `true`, migrations from all subdirectories are resolved into a single set
+    /// ordered by version, and it is an error for two migrations to have the same version.
+    /// SQL files without an integer version prefix are skipped with a warning instead of an error.
+    pub fn set_recursive(&mut self, recursive: bool) -> &mut Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Returns `true` if migrations will be resolved from subdirectories.
+    pub fn recursive(&self) -> bool {
+        self.recursive
+    }
+
+    /// Specify whether to check that migrations don't mix timestamp and sequential versions.
+    ///
+    /// Defaults to [`VersioningConsistency::Allow`].
+    pub fn set_versioning_consistency(
+        &mut self,
+        versioning_consistency: VersioningConsistency,
+    ) -> &mut Self {
+        self.versioning_consistency = versioning_consistency;
+        self
+    }
+
+    /// Specify what to do with migration files that are not valid UTF-8.
+    ///
+    /// Defaults to [`NonUtf8Policy::Error`].
+    pub fn set_non_utf8_policy(&mut self, non_utf8_policy: NonUtf8Policy) -> &mut Self {
+        self.non_utf8_policy = non_utf8_policy;
+        self
+    }
+
     /// Ignore a character when hashing migrations.
     ///
     /// The migration SQL string itself will still contain the character,
@@ -156,44 +246,32 @@ pub fn resolve_blocking(path: &Path) -> Result<Vec<(Migration, PathBuf)>, Resolv
 pub fn resolve_blocking_with_config(
     path: &Path,
     config: &ResolveConfig,
+) -> Result<Vec<(Migration, PathBuf)>, ResolveError> {
+    resolve_blocking_with_diagnostics(path, config, &mut Diagnostics::new())
+}
+
+/// Like [`resolve_blocking_with_config()`], but pushes any warnings into `diagnostics`.
+#[doc(hidden)]
+pub fn resolve_blocking_with_diagnostics(
+    path: &Path,
+    config: &ResolveConfig,
+    diagnostics: &mut Diagnostics,
 ) -> Result<Vec<(Migration, PathBuf)>, ResolveError> {
     let path = path.canonicalize().map_err(|e| ResolveError {
         message: format!("error canonicalizing path {}", path.display()),
         source: Some(e),
     })?;
 
-    let s = fs::read_dir(&path).map_err(|e| ResolveError {
-        message: format!("error reading migration directory {}", path.display()),
-        source: Some(e),
-    })?;
+    let mut entry_paths = Vec::new();
+    list_files(&path, config.recursive, &mut entry_paths)?;
 
     let mut migrations = Vec::new();
 
-    for res in s {
-        let entry = res.map_err(|e| ResolveError {
-            message: format!(
-                "error reading contents of migration directory {}",
-                path.display()
-            ),
-            source: Some(e),
-        })?;
-
-        let entry_path = entry.path();
-
-        let metadata = fs::metadata(&entry_path).map_err(|e| ResolveError {
-            message: format!(
-                "error getting metadata of migration path {}",
-                entry_path.display()
-            ),
-            source: Some(e),
-        })?;
-
-        if !metadata.is_file() {
-            // not a file; ignore
+    for entry_path in entry_paths {
+        let Some(file_name) = entry_path.file_name() else {
             continue;
-        }
+        };
 
-        let file_name = entry.file_name();
         // This is arguably the wrong choice,
         // but it really only matters for parsing the version and description.
         //
@@ -205,14 +283,33 @@ pub fn resolve_blocking_with_config(
 
         if parts.len() != 2 || !parts[1].ends_with(".sql") {
             // not of the format: <VERSION>_<DESCRIPTION>.<REVERSIBLE_DIRECTION>.sql; ignore
+            if file_name.ends_with(".sql") {
+                diagnostics.warn(
+                    Some(Location::File(entry_path.clone())),
+                    "SQL file ignored; migrations must be named `<VERSION>_<DESCRIPTION>.sql`",
+                );
+            }
+
             continue;
         }
 
-        let version: i64 = parts[0].parse()
-            .map_err(|_e| ResolveError {
-                message: format!("error parsing migration filename {file_name:?}; expected integer version prefix (e.g. `01_foo.sql`)"),
+        let Ok(version) = parts[0].parse::<i64>() else {
+            // Subdirectories commonly hold other SQL files, e.g. `seeds/` or `fixtures/`.
+            if config.recursive {
+                diagnostics.warn(
+                    Some(Location::File(entry_path.clone())),
+                    "SQL file ignored; expected integer version prefix (e.g. `01_foo.sql`)",
+                );
+                continue;
+            }
+
+            let relative_path = entry_path.strip_prefix(&path).unwrap_or(&entry_path);
+
+            return Err(ResolveError {
+                message: format!("error parsing migration filename {relative_path:?}; expected integer version prefix (e.g. `01_foo.sql`)"),
                 source: None,
-            })?;
+            });
+        };
 
         let migration_type = MigrationType::from_filename(parts[1]);
 
@@ -222,7 +319,7 @@ pub fn resolve_blocking_with_config(
             .replace('_', " ")
             .to_owned();
 
-        let sql = fs::read_to_string(&entry_path).map_err(|e| ResolveError {
+        let sql = fs::read(&entry_path).map_err(|e| ResolveError {
             message: format!(
                 "error reading contents of migration {}: {e}",
                 entry_path.display()
@@ -230,6 +327,29 @@ pub fn resolve_blocking_with_config(
             source: Some(e),
         })?;
 
+        let sql = match String::from_utf8(sql) {
+            Ok(sql) => sql,
+            Err(e) => match config.non_utf8_policy {
+                NonUtf8Policy::Error => {
+                    return Err(ResolveError {
+                        message: format!(
+                            "migration {} is not valid UTF-8: {}",
+                            entry_path.display(),
+                            e.utf8_error()
+                        ),
+                        source: None,
+                    });
+                }
+                NonUtf8Policy::Skip => {
+                    diagnostics.warn(
+                        Some(Location::File(entry_path.clone())),
+                        format!("migration skipped; not valid UTF-8: {}", e.utf8_error()),
+                    );
+                    continue;
+                }
+            },
+        };
+
         // opt-out of migration transaction
         let no_tx = sql.starts_with("-- no-transaction");
 
@@ -251,9 +371,136 @@ pub fn resolve_blocking_with_config(
     // Ensure that we are sorted by version in ascending order.
     migrations.sort_by_key(|(m, _)| m.version);
 
+    if config.recursive {
+        check_duplicate_versions(&migrations)?;
+    }
+
+    check_versioning_consistency(
+        &path,
+        &migrations,
+        config.versioning_consistency,
+        diagnostics,
+    )?;
+
     Ok(migrations)
 }
 
+/// Push the paths of all files in `dir` to `files`, descending into subdirectories if `recursive`.
+fn list_files(dir: &Path, recursive: bool, files: &mut Vec<PathBuf>) -> Result<(), ResolveError> {
+    let s = fs::read_dir(dir).map_err(|e| ResolveError {
+        message: format!("error reading migration directory {}", dir.display()),
+        source: Some(e),
+    })?;
+
+    for res in s {
+        let entry = res.map_err(|e| ResolveError {
+            message: format!(
+                "error reading contents of migration directory {}",
+                dir.display()
+            ),
+            source: Some(e),
+        })?;
+
+        let entry_path = entry.path();
+
+        let metadata = fs::metadata(&entry_path).map_err(|e| ResolveError {
+            message: format!(
+                "error getting metadata of migration path {}",
+                entry_path.display()
+            ),
+            source: Some(e),
+        })?;
+
+        if metadata.is_file() {
+            files.push(entry_path);
+        } else if recursive && metadata.is_dir() {
+            // Don't follow symlinks to directories in case they form a cycle.
+            let is_symlink = entry.file_type().is_ok_and(|ty| ty.is_symlink());
+
+            if !is_symlink {
+                list_files(&entry_path, recursive, files)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Error if more than one migration file has the same version and direction,
+/// which can happen when migrations are spread across subdirectories.
+///
+/// `migrations` must be sorted by version.
+fn check_duplicate_versions(migrations: &[(Migration, PathBuf)]) -> Result<(), ResolveError> {
+    for (i, (migration, path)) in migrations.iter().enumerate() {
+        let duplicate = migrations[i + 1..]
+            .iter()
+            .take_while(|(other, _)| other.version == migration.version)
+            .find(|(other, _)| {
+                other.migration_type.is_down_migration()
+                    == migration.migration_type.is_down_migration()
+            });
+
+        if let Some((_, other_path)) = duplicate {
+            return Err(ResolveError {
+                message: format!(
+                    "duplicate migration version {}: {} and {}",
+                    migration.version,
+                    path.display(),
+                    other_path.display()
+                ),
+                source: None,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Check whether `migrations` mix timestamp and sequential versions,
+/// and report it according to `versioning_consistency`.
+fn check_versioning_consistency(
+    path: &Path,
+    migrations: &[(Migration, PathBuf)],
+    versioning_consistency: VersioningConsistency,
+    diagnostics: &mut Diagnostics,
+) -> Result<(), ResolveError> {
+    if versioning_consistency == VersioningConsistency::Allow {
+        return Ok(());
+    }
+
+    let find = |scheme: VersioningScheme| {
+        migrations
+            .iter()
+            .map(|(m, _)| m.version)
+            .find(|&version| VersioningScheme::of_version(version) == scheme)
+    };
+
+    let (timestamp, sequential) = (
+        find(VersioningScheme::Timestamp),
+        find(VersioningScheme::Sequential),
+    );
+
+    let (Some(timestamp), Some(sequential)) = (timestamp, sequential) else {
+        return Ok(());
+    };
+
+    let message = format!(
+        "migrations mix timestamp versions (e.g. {timestamp}) and sequential versions (e.g. {sequential})"
+    );
+
+    match versioning_consistency {
+        VersioningConsistency::Allow => Ok(()),
+        VersioningConsistency::Warn => {
+            diagnostics.warn(Some(Location::File(path.to_path_buf())), message);
+            Ok(())
+        }
+        VersioningConsistency::Error => Err(ResolveError {
+            message,
+            source: None,
+        }),
+    }
+}
+
 fn checksum_with(sql: &str, ignored_chars: &BTreeSet<char>) -> Vec<u8> {
     if ignored_chars.is_empty() {
         // This is going to be much faster because it doesn't have to UTF-8 decode `sql`.
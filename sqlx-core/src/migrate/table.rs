@@ -0,0 +1,408 @@
+use crate::config::diagnostics::Diagnostics;
+use crate::config::migrate::InstalledOnType;
+use crate::migrate::{Migrate, MigrateError, Migration};
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The database flavors with built-in migration support.
+///
+/// Used to generate SQL for the table which tracks applied migrations,
+/// which differs slightly between databases.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DatabaseKind {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl DatabaseKind {
+    /// The `CREATE TABLE IF NOT EXISTS` statement for the migrations table.
+    pub fn create_migrations_table_sql(
+        self,
+        table_name: &str,
+        installed_on_type: InstalledOnType,
+    ) -> String {
+        let checksum_type = match self {
+            Self::Postgres => "BYTEA",
+            Self::MySql | Self::Sqlite => "BLOB",
+        };
+
+        let installed_on_type = installed_on_type.column_type(self);
+
+        let now = match self {
+            Self::Postgres => "now()",
+            Self::MySql | Self::Sqlite => "CURRENT_TIMESTAMP",
+        };
+
+        format!(
+            r#"
+CREATE TABLE IF NOT EXISTS {table_name} (
+    version BIGINT PRIMARY KEY,
+    description TEXT NOT NULL,
+    installed_on {installed_on_type} NOT NULL DEFAULT {now},
+    success BOOLEAN NOT NULL,
+    checksum {checksum_type} NOT NULL,
+    execution_time BIGINT NOT NULL
+);
+                "#
+        )
+    }
+
+    /// The `INSERT` statement recording a migration in the migrations table.
+    ///
+    /// Binds `version`, `description` and `checksum`, in that order.
+    ///
+    /// If `installed_on_type` is [`InstalledOnType::TimestampUtc`],
+    /// `now` is written to `installed_on` explicitly.
+    pub fn insert_migration_sql(
+        self,
+        table_name: &str,
+        success: bool,
+        installed_on_type: InstalledOnType,
+        now: SystemTime,
+    ) -> String {
+        let [version, description, checksum] = match self {
+            Self::Postgres => ["$1", "$2", "$3"],
+            Self::MySql => ["?", "?", "?"],
+            Self::Sqlite => ["?1", "?2", "?3"],
+        };
+
+        let success = if success { "TRUE" } else { "FALSE" };
+
+        match installed_on_type.utc_value(now) {
+            Some(installed_on) => format!(
+                r#"
+    INSERT INTO {table_name} ( version, description, installed_on, success, checksum, execution_time )
+    VALUES ( {version}, {description}, '{installed_on}', {success}, {checksum}, -1 )
+                "#
+            ),
+            None => format!(
+                r#"
+    INSERT INTO {table_name} ( version, description, success, checksum, execution_time )
+    VALUES ( {version}, {description}, {success}, {checksum}, -1 )
+                "#
+            ),
+        }
+    }
+
+    /// The `CREATE SCHEMA IF NOT EXISTS` statement for the given schema,
+    /// or `None` if `schema_name` is empty or the database cannot create schemas
+    /// with a single statement.
+    pub fn create_schema_sql(self, schema_name: &str) -> Option<String> {
+        if schema_name.is_empty() {
+            return None;
+        }
+
+        match self {
+            Self::Postgres | Self::MySql => {
+                Some(format!("CREATE SCHEMA IF NOT EXISTS {schema_name};"))
+            }
+            // Schemas in SQLite are attached database files.
+            Self::Sqlite => None,
+        }
+    }
+
+    /// The statement beginning a transaction.
+    pub fn begin_sql(self) -> &'static str {
+        match self {
+            Self::MySql => "START TRANSACTION;",
+            Self::Postgres | Self::Sqlite => "BEGIN;",
+        }
+    }
+
+    /// Like [`Self::insert_migration_sql()`], but with the values of `migration` written inline
+    /// instead of bound as parameters, for use in a standalone SQL script.
+    ///
+    /// If `installed_on_type` is [`InstalledOnType::TimestampUtc`], `installed_on` is set to
+    /// the current UTC time of the database server when the script is run.
+    ///
+    /// For MySQL, the description is written as a hex literal so it is not affected by `sql_mode`.
+    pub fn insert_migration_literal_sql(
+        self,
+        table_name: &str,
+        migration: &Migration,
+        installed_on_type: InstalledOnType,
+    ) -> String {
+        let version = migration.version;
+
+        let description = match self {
+            // With the default `sql_mode`, MySQL treats `\` in string literals as an escape.
+            Self::MySql => format!("X'{}'", hex(migration.description.as_bytes())),
+            Self::Postgres | Self::Sqlite => {
+                format!("'{}'", migration.description.replace('\'', "''"))
+            }
+        };
+
+        let checksum = hex(&migration.checksum);
+
+        let checksum = match self {
+            Self::Postgres => format!("decode('{checksum}', 'hex')"),
+            Self::MySql | Self::Sqlite => format!("X'{checksum}'"),
+        };
+
+        match installed_on_type.utc_expression(self) {
+            Some(installed_on) => format!(
+                "INSERT INTO {table_name} ( version, description, installed_on, success, checksum, execution_time )\n\
+                 VALUES ( {version}, {description}, {installed_on}, TRUE, {checksum}, -1 );"
+            ),
+            None => format!(
+                "INSERT INTO {table_name} ( version, description, success, checksum, execution_time )\n\
+                 VALUES ( {version}, {description}, TRUE, {checksum}, -1 );"
+            ),
+        }
+    }
+}
+
+impl InstalledOnType {
+    /// The SQL type of the `installed_on` column for the given database.
+    pub fn column_type(self, kind: DatabaseKind) -> &'static str {
+        match (self, kind) {
+            (Self::TimestampTz, DatabaseKind::Postgres) => "TIMESTAMPTZ",
+            // MySQL converts `TIMESTAMP` values from the session time zone to UTC for storage.
+            (Self::TimestampTz, DatabaseKind::MySql) => "TIMESTAMP",
+            (Self::Timestamp | Self::TimestampUtc, DatabaseKind::Postgres) => "TIMESTAMP",
+            (Self::Timestamp | Self::TimestampUtc, DatabaseKind::MySql) => "DATETIME",
+            // SQLite has no timezone-aware type; `CURRENT_TIMESTAMP` is always UTC.
+            (_, DatabaseKind::Sqlite) => "TIMESTAMP",
+        }
+    }
+
+    /// Returns `true` if `column_type`, as reported by the database, is the type
+    /// [`DatabaseKind::create_migrations_table_sql()`] would use for the `installed_on` column.
+    pub fn matches_column_type(self, kind: DatabaseKind, column_type: &str) -> bool {
+        self.column_type(kind).eq_ignore_ascii_case(column_type)
+    }
+
+    /// An SQL expression for the current UTC time on the database server
+    /// for [`Self::TimestampUtc`], or `None` to use the column default.
+    pub fn utc_expression(self, kind: DatabaseKind) -> Option<&'static str> {
+        if self != Self::TimestampUtc {
+            return None;
+        }
+
+        Some(match kind {
+            DatabaseKind::Postgres => "(now() AT TIME ZONE 'UTC')",
+            DatabaseKind::MySql => "UTC_TIMESTAMP(6)",
+            DatabaseKind::Sqlite => "CURRENT_TIMESTAMP",
+        })
+    }
+
+    /// The value the migrator should explicitly write to `installed_on`, if any.
+    ///
+    /// Returns `now` formatted as a UTC `YYYY-MM-DD HH:MM:SS.ffffff` timestamp
+    /// for [`Self::TimestampUtc`], or `None` to use the column default.
+    pub fn utc_value(self, now: SystemTime) -> Option<String> {
+        if self != Self::TimestampUtc {
+            return None;
+        }
+
+        // Times before 1970 aren't a concern for a migration timestamp.
+        let since_epoch = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let secs = since_epoch.as_secs();
+
+        let (year, month, day) = civil_from_days(secs / 86_400);
+        let secs_of_day = secs % 86_400;
+
+        Some(format!(
+            "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}.{:06}",
+            secs_of_day / 3600,
+            secs_of_day / 60 % 60,
+            secs_of_day % 60,
+            since_epoch.subsec_micros(),
+        ))
+    }
+}
+
+/// Warn if the `installed_on` column of an existing migrations table does not have
+/// the type `installed_on_type` would create, since existing tables are never altered.
+#[doc(hidden)]
+pub async fn check_installed_on_type<C: Migrate + ?Sized>(
+    conn: &mut C,
+    table_name: &str,
+    installed_on_type: InstalledOnType,
+    diagnostics: &mut Diagnostics,
+) -> Result<(), MigrateError> {
+    if let Some(column_type) = conn
+        .installed_on_type_mismatch(table_name, installed_on_type)
+        .await?
+    {
+        diagnostics.warn(
+            None,
+            format!(
+                "the `installed_on` column of {table_name} has type {column_type}, \
+                 which does not match installed-on-type {installed_on_type:?}; \
+                 the existing column will not be altered"
+            ),
+        );
+    }
+
+    Ok(())
+}
+
+/// Encode `bytes` as lowercase hexadecimal.
+fn hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        write!(hex, "{b:02x}").expect("should not fail to write to String");
+    }
+    hex
+}
+
+/// Convert days since the Unix epoch to a `(year, month, day)` date in the proleptic Gregorian calendar.
+///
+/// Adapted from <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migrate::MigrationType;
+    use crate::sql_str::SqlSafeStr;
+    use std::time::Duration;
+
+    #[test]
+    fn create_table_uses_installed_on_type() {
+        let sql = DatabaseKind::Postgres
+            .create_migrations_table_sql("_sqlx_migrations", InstalledOnType::TimestampTz);
+        assert!(sql.contains("installed_on TIMESTAMPTZ NOT NULL DEFAULT now()"));
+
+        let sql = DatabaseKind::Postgres
+            .create_migrations_table_sql("_sqlx_migrations", InstalledOnType::TimestampUtc);
+        assert!(sql.contains("installed_on TIMESTAMP NOT NULL DEFAULT now()"));
+
+        let sql = DatabaseKind::MySql
+            .create_migrations_table_sql("_sqlx_migrations", InstalledOnType::Timestamp);
+        assert!(sql.contains("installed_on DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP"));
+    }
+
+    #[test]
+    fn create_schema_skips_empty_name() {
+        assert_eq!(
+            DatabaseKind::Postgres.create_schema_sql("app").as_deref(),
+            Some("CREATE SCHEMA IF NOT EXISTS app;")
+        );
+        assert_eq!(DatabaseKind::Postgres.create_schema_sql(""), None);
+        assert_eq!(DatabaseKind::MySql.create_schema_sql(""), None);
+        assert_eq!(DatabaseKind::Sqlite.create_schema_sql("app"), None);
+    }
+
+    #[test]
+    fn matches_column_type() {
+        assert!(
+            InstalledOnType::TimestampTz.matches_column_type(DatabaseKind::Postgres, "TIMESTAMPTZ")
+        );
+        assert!(!InstalledOnType::TimestampUtc
+            .matches_column_type(DatabaseKind::Postgres, "TIMESTAMPTZ"));
+        assert!(InstalledOnType::Timestamp.matches_column_type(DatabaseKind::MySql, "datetime"));
+        assert!(!InstalledOnType::Timestamp.matches_column_type(DatabaseKind::MySql, "timestamp"));
+    }
+
+    #[test]
+    fn insert_writes_utc_installed_on() {
+        // 2024-02-29T13:45:30.123456Z
+        let now = UNIX_EPOCH + Duration::from_micros(1_709_214_330_123_456);
+
+        let sql = DatabaseKind::Postgres.insert_migration_sql(
+            "_sqlx_migrations",
+            true,
+            InstalledOnType::TimestampUtc,
+            now,
+        );
+        assert!(
+            sql.contains("VALUES ( $1, $2, '2024-02-29 13:45:30.123456', TRUE, $3, -1 )"),
+            "{sql}"
+        );
+
+        let sql = DatabaseKind::Postgres.insert_migration_sql(
+            "_sqlx_migrations",
+            true,
+            InstalledOnType::TimestampTz,
+            now,
+        );
+        assert!(!sql.contains("installed_on"), "{sql}");
+    }
+
+    #[test]
+    fn insert_literal_uses_server_utc_time() {
+        let migration = Migration::new(
+            1,
+            "create users".into(),
+            MigrationType::Simple,
+            "CREATE TABLE users ( id BIGINT PRIMARY KEY );".into_sql_str(),
+            false,
+        );
+
+        for (kind, description, expression) in [
+            (
+                DatabaseKind::Postgres,
+                "'create users'",
+                "(now() AT TIME ZONE 'UTC')",
+            ),
+            (
+                DatabaseKind::MySql,
+                "X'637265617465207573657273'",
+                "UTC_TIMESTAMP(6)",
+            ),
+            (DatabaseKind::Sqlite, "'create users'", "CURRENT_TIMESTAMP"),
+        ] {
+            let sql = kind.insert_migration_literal_sql(
+                "_sqlx_migrations",
+                &migration,
+                InstalledOnType::TimestampUtc,
+            );
+            assert!(
+                sql.contains(&format!("VALUES ( 1, {description}, {expression}, TRUE, ")),
+                "{sql}"
+            );
+        }
+    }
+
+    #[test]
+    fn insert_literal_escapes_description() {
+        let migration = Migration::new(
+            1,
+            r"user's \ index".into(),
+            MigrationType::Simple,
+            "CREATE INDEX ON users ( id );".into_sql_str(),
+            false,
+        );
+
+        let sql = DatabaseKind::Postgres.insert_migration_literal_sql(
+            "_sqlx_migrations",
+            &migration,
+            InstalledOnType::TimestampTz,
+        );
+        assert!(
+            sql.contains(r"VALUES ( 1, 'user''s \ index', TRUE, "),
+            "{sql}"
+        );
+
+        // Written as a hex literal so a `\` can't act as an escape, whatever the `sql_mode`.
+        let sql = DatabaseKind::MySql.insert_migration_literal_sql(
+            "_sqlx_migrations",
+            &migration,
+            InstalledOnType::TimestampTz,
+        );
+        assert!(
+            sql.contains(&format!(
+                "VALUES ( 1, X'{}', TRUE, ",
+                hex(r"user's \ index".as_bytes())
+            )),
+            "{sql}"
+        );
+        assert!(!sql.contains('\\'), "{sql}");
+    }
+}
@@ -0,0 +1,124 @@
+/// The scheme a migration version was created with.
+///
+/// See [`Self::infer()`] for the classification behind `DefaultVersioning::Inferred`
+/// in `sqlx migrate add`, and [`Self::of_version()`] for the one used to check
+/// [`VersioningConsistency`][crate::config::migrate::VersioningConsistency].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VersioningScheme {
+    /// Versions are UTC timestamps (`YYYYMMDDHHMMSS`).
+    Timestamp,
+    /// Versions are sequential integers.
+    Sequential,
+}
+
+impl VersioningScheme {
+    /// Infer the scheme of `version` given the `previous` (next lowest) version, if any.
+    ///
+    /// * If `version` is one more than `previous`, infer `Sequential`.
+    /// * If there is no `previous` version and `version` is `0` or `1`, infer `Sequential`.
+    /// * Otherwise, infer `Timestamp`.
+    pub fn infer(previous: Option<i64>, version: i64) -> Self {
+        let sequential = match previous {
+            Some(previous) => version.checked_sub(previous) == Some(1),
+            None => matches!(version, 0 | 1),
+        };
+
+        if sequential {
+            Self::Sequential
+        } else {
+            Self::Timestamp
+        }
+    }
+
+    /// Classify a single `version` on its own.
+    ///
+    /// Versions that parse as a 14-digit `YYYYMMDDHHMMSS` timestamp are `Timestamp`,
+    /// all others are `Sequential`.
+    pub fn of_version(version: i64) -> Self {
+        if !(10_000_000_000_000..100_000_000_000_000).contains(&version) {
+            return Self::Sequential;
+        }
+
+        let month = version / 100_000_000 % 100;
+        let day = version / 1_000_000 % 100;
+        let hour = version / 10_000 % 100;
+        let minute = version / 100 % 100;
+        let second = version % 100;
+
+        if (1..=12).contains(&month)
+            && (1..=31).contains(&day)
+            && hour < 24
+            && minute < 60
+            && second < 60
+        {
+            Self::Timestamp
+        } else {
+            Self::Sequential
+        }
+    }
+}
+
+#[test]
+fn infer_versioning_scheme() {
+    assert_eq!(
+        VersioningScheme::infer(None, 0),
+        VersioningScheme::Sequential
+    );
+    assert_eq!(
+        VersioningScheme::infer(None, 1),
+        VersioningScheme::Sequential
+    );
+    assert_eq!(
+        VersioningScheme::infer(None, 2),
+        VersioningScheme::Timestamp
+    );
+    assert_eq!(
+        VersioningScheme::infer(Some(1), 2),
+        VersioningScheme::Sequential
+    );
+    assert_eq!(
+        VersioningScheme::infer(Some(1), 3),
+        VersioningScheme::Timestamp
+    );
+    assert_eq!(
+        VersioningScheme::infer(None, 20240101000000),
+        VersioningScheme::Timestamp
+    );
+    assert_eq!(
+        VersioningScheme::infer(Some(3), 20240101000000),
+        VersioningScheme::Timestamp
+    );
+    assert_eq!(
+        VersioningScheme::infer(Some(20240101000000), 20240101000001),
+        VersioningScheme::Sequential
+    );
+    assert_eq!(
+        VersioningScheme::infer(Some(i64::MIN), i64::MAX),
+        VersioningScheme::Timestamp
+    );
+}
+
+#[test]
+fn versioning_scheme_of_version() {
+    assert_eq!(
+        VersioningScheme::of_version(20240101000000),
+        VersioningScheme::Timestamp
+    );
+    assert_eq!(
+        VersioningScheme::of_version(20240101000001),
+        VersioningScheme::Timestamp
+    );
+    assert_eq!(
+        VersioningScheme::of_version(1),
+        VersioningScheme::Sequential
+    );
+    assert_eq!(
+        VersioningScheme::of_version(4),
+        VersioningScheme::Sequential
+    );
+    // 14 digits, but month 13
+    assert_eq!(
+        VersioningScheme::of_version(20241301000000),
+        VersioningScheme::Sequential
+    );
+}
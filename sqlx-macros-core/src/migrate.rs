@@ -5,8 +5,9 @@ use std::path::{Path, PathBuf};
 
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens, TokenStreamExt};
+use sqlx_core::config::diagnostics::{Diagnostic, Diagnostics};
 use sqlx_core::config::Config;
-use sqlx_core::migrate::{Migration, MigrationType};
+use sqlx_core::migrate::{InstalledOnType, Migration, MigrationType};
 use syn::LitStr;
 
 pub const DEFAULT_PATH: &str = "./migrations";
@@ -26,6 +27,44 @@ impl ToTokens for QuoteMigrationType {
     }
 }
 
+pub struct QuoteInstalledOnType(InstalledOnType);
+
+impl ToTokens for QuoteInstalledOnType {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ts = match self.0 {
+            InstalledOnType::TimestampTz => {
+                quote! { ::sqlx::migrate::InstalledOnType::TimestampTz }
+            }
+            InstalledOnType::Timestamp => quote! { ::sqlx::migrate::InstalledOnType::Timestamp },
+            InstalledOnType::TimestampUtc => {
+                quote! { ::sqlx::migrate::InstalledOnType::TimestampUtc }
+            }
+        };
+        tokens.append_all(ts);
+    }
+}
+
+/// Emits a [`Diagnostic`] as a compiler warning.
+///
+/// Proc macros can't emit warnings on stable, so this uses a deprecated item instead.
+struct QuoteWarning<'a>(&'a Diagnostic);
+
+impl ToTokens for QuoteWarning<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let note = format!("sqlx::migrate!(): {}", self.0);
+
+        tokens.append_all(quote! {
+            const _: () = {
+                #[deprecated(note = #note)]
+                #[allow(non_upper_case_globals)]
+                const sqlx_migrate_warning: () = ();
+
+                sqlx_migrate_warning
+            };
+        });
+    }
+}
+
 struct QuoteMigration {
     migration: Migration,
     path: PathBuf,
@@ -112,18 +151,29 @@ pub fn expand_with_path(config: &Config, path: &Path) -> crate::Result<TokenStre
 
     let resolve_config = config.migrate.to_resolve_config();
 
+    let mut diagnostics = Diagnostics::new();
+
     // Use the same code path to resolve migrations at compile time and runtime.
-    let migrations = sqlx_core::migrate::resolve_blocking_with_config(&path, &resolve_config)?
-        .into_iter()
-        .map(|(migration, path)| QuoteMigration { migration, path });
+    let migrations = sqlx_core::migrate::resolve_blocking_with_diagnostics(
+        &path,
+        &resolve_config,
+        &mut diagnostics,
+    )?
+    .into_iter()
+    .map(|(migration, path)| QuoteMigration { migration, path });
+
+    let warnings = diagnostics.iter().map(QuoteWarning);
 
     let table_name = config.migrate.table_name();
     let table_name_str = table_name.as_str();
 
-    let create_schemas = config.migrate.create_schemas.iter().map(|schema_name| {
+    let create_schemas = config.migrate.create_schemas_ordered()?;
+    let create_schemas = create_schemas.iter().map(|schema_name| {
         quote! { ::std::borrow::Cow::Borrowed(#schema_name) }
     });
 
+    let installed_on_type = QuoteInstalledOnType(config.migrate.installed_on_type);
+
     #[cfg(any(sqlx_macros_unstable, procmacro2_semver_exempt))]
     {
         let path = path.to_str().ok_or_else(|| {
@@ -138,11 +188,16 @@ pub fn expand_with_path(config: &Config, path: &Path) -> crate::Result<TokenStre
 
     Ok(quote! {
         ::sqlx::migrate::Migrator {
-            migrations: ::std::borrow::Cow::Borrowed(const {&[
+            migrations: ::std::borrow::Cow::Borrowed(const {
+                #(#warnings)*
+
+                &[
                     #(#migrations),*
-            ]}),
+                ]
+            }),
             create_schemas: ::std::borrow::Cow::Borrowed(&[#(#create_schemas),*]),
             table_name: ::std::borrow::Cow::Borrowed(#table_name_str),
+            installed_on_type: #installed_on_type,
             ..::sqlx::migrate::Migrator::DEFAULT
         }
     })
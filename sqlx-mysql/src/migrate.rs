@@ -1,10 +1,13 @@
 use std::str::FromStr;
 use std::time::Duration;
 use std::time::Instant;
+use std::time::SystemTime;
 
 use futures_core::future::BoxFuture;
+use sqlx_core::column::Column;
 pub(crate) use sqlx_core::migrate::*;
-use sqlx_core::sql_str::AssertSqlSafe;
+use sqlx_core::sql_str::{AssertSqlSafe, SqlSafeStr};
+use sqlx_core::type_info::TypeInfo;
 
 use crate::connection::{ConnectOptions, Connection};
 use crate::error::Error;
@@ -77,11 +80,9 @@ impl Migrate for MySqlConnection {
         schema_name: &'e str,
     ) -> BoxFuture<'e, Result<(), MigrateError>> {
         Box::pin(async move {
-            // language=SQL
-            self.execute(AssertSqlSafe(format!(
-                r#"CREATE SCHEMA IF NOT EXISTS {schema_name};"#
-            )))
-            .await?;
+            if let Some(sql) = DatabaseKind::MySql.create_schema_sql(schema_name) {
+                self.execute(AssertSqlSafe(sql)).await?;
+            }
 
             Ok(())
         })
@@ -90,27 +91,49 @@ impl Migrate for MySqlConnection {
     fn ensure_migrations_table<'e>(
         &'e mut self,
         table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        self.ensure_migrations_table_with(table_name, InstalledOnType::default())
+    }
+
+    fn ensure_migrations_table_with<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        installed_on_type: InstalledOnType,
     ) -> BoxFuture<'e, Result<(), MigrateError>> {
         Box::pin(async move {
-            // language=MySQL
-            self.execute(AssertSqlSafe(format!(
-                r#"
-CREATE TABLE IF NOT EXISTS {table_name} (
-    version BIGINT PRIMARY KEY,
-    description TEXT NOT NULL,
-    installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
-    success BOOLEAN NOT NULL,
-    checksum BLOB NOT NULL,
-    execution_time BIGINT NOT NULL
-);
-                "#
-            )))
+            self.execute(AssertSqlSafe(
+                DatabaseKind::MySql.create_migrations_table_sql(table_name, installed_on_type),
+            ))
             .await?;
 
             Ok(())
         })
     }
 
+    fn installed_on_type_mismatch<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        installed_on_type: InstalledOnType,
+    ) -> BoxFuture<'e, Result<Option<String>, MigrateError>> {
+        Box::pin(async move {
+            let describe = self
+                .describe(
+                    AssertSqlSafe(format!("SELECT installed_on FROM {table_name}")).into_sql_str(),
+                )
+                .await?;
+
+            let column_type = describe
+                .columns()
+                .first()
+                .map(|column| column.type_info().name())
+                .filter(|column_type| {
+                    !installed_on_type.matches_column_type(DatabaseKind::MySql, column_type)
+                });
+
+            Ok(column_type.map(str::to_owned))
+        })
+    }
+
     fn dirty_version<'e>(
         &'e mut self,
         table_name: &'e str,
@@ -191,6 +214,15 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         &'e mut self,
         table_name: &'e str,
         migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
+        self.apply_with(table_name, migration, InstalledOnType::default())
+    }
+
+    fn apply_with<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+        installed_on_type: InstalledOnType,
     ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
         Box::pin(async move {
             // Use a single transaction for the actual migration script and the essential bookeeping so we never
@@ -206,13 +238,11 @@ CREATE TABLE IF NOT EXISTS {table_name} (
             //
             // To somewhat try to detect this, we first insert the migration into the migration table with
             // `success=FALSE` and later modify the flag.
-            //
-            // language=MySQL
-            let _ = query(AssertSqlSafe(format!(
-                r#"
-    INSERT INTO {table_name} ( version, description, success, checksum, execution_time )
-    VALUES ( ?, ?, FALSE, ?, -1 )
-                "#
+            let _ = query(AssertSqlSafe(DatabaseKind::MySql.insert_migration_sql(
+                table_name,
+                false,
+                installed_on_type,
+                SystemTime::now(),
             )))
             .bind(migration.version)
             .bind(&*migration.description)
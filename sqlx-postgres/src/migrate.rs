@@ -1,13 +1,17 @@
 use std::str::FromStr;
 use std::time::Duration;
 use std::time::Instant;
+use std::time::SystemTime;
 
 use futures_core::future::BoxFuture;
 
+use sqlx_core::column::Column;
 pub(crate) use sqlx_core::migrate::MigrateError;
 pub(crate) use sqlx_core::migrate::{AppliedMigration, Migration};
+use sqlx_core::migrate::{DatabaseKind, InstalledOnType};
 pub(crate) use sqlx_core::migrate::{Migrate, MigrateDatabase};
-use sqlx_core::sql_str::AssertSqlSafe;
+use sqlx_core::sql_str::{AssertSqlSafe, SqlSafeStr};
+use sqlx_core::type_info::TypeInfo;
 
 use crate::connection::{ConnectOptions, Connection};
 use crate::error::Error;
@@ -110,11 +114,8 @@ impl Migrate for PgConnection {
     ) -> BoxFuture<'e, Result<(), MigrateError>> {
         Box::pin(async move {
             // Create the schema if it's not empty
-            if !schema_name.is_empty() {
-                self.execute(AssertSqlSafe(format!(
-                    r#"CREATE SCHEMA IF NOT EXISTS {schema_name};"#
-                )))
-                .await?;
+            if let Some(sql) = DatabaseKind::Postgres.create_schema_sql(schema_name) {
+                self.execute(AssertSqlSafe(sql)).await?;
             }
 
             Ok(())
@@ -124,27 +125,49 @@ impl Migrate for PgConnection {
     fn ensure_migrations_table<'e>(
         &'e mut self,
         table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        self.ensure_migrations_table_with(table_name, InstalledOnType::default())
+    }
+
+    fn ensure_migrations_table_with<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        installed_on_type: InstalledOnType,
     ) -> BoxFuture<'e, Result<(), MigrateError>> {
         Box::pin(async move {
-            // language=SQL
-            self.execute(AssertSqlSafe(format!(
-                r#"
-CREATE TABLE IF NOT EXISTS {table_name} (
-    version BIGINT PRIMARY KEY,
-    description TEXT NOT NULL,
-    installed_on TIMESTAMPTZ NOT NULL DEFAULT now(),
-    success BOOLEAN NOT NULL,
-    checksum BYTEA NOT NULL,
-    execution_time BIGINT NOT NULL
-);
-                "#
-            )))
+            self.execute(AssertSqlSafe(
+                DatabaseKind::Postgres.create_migrations_table_sql(table_name, installed_on_type),
+            ))
             .await?;
 
             Ok(())
         })
     }
 
+    fn installed_on_type_mismatch<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        installed_on_type: InstalledOnType,
+    ) -> BoxFuture<'e, Result<Option<String>, MigrateError>> {
+        Box::pin(async move {
+            let describe = self
+                .describe(
+                    AssertSqlSafe(format!("SELECT installed_on FROM {table_name}")).into_sql_str(),
+                )
+                .await?;
+
+            let column_type = describe
+                .columns()
+                .first()
+                .map(|column| column.type_info().name())
+                .filter(|column_type| {
+                    !installed_on_type.matches_column_type(DatabaseKind::Postgres, column_type)
+                });
+
+            Ok(column_type.map(str::to_owned))
+        })
+    }
+
     fn dirty_version<'e>(
         &'e mut self,
         table_name: &'e str,
@@ -225,13 +248,22 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         &'e mut self,
         table_name: &'e str,
         migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
+        self.apply_with(table_name, migration, InstalledOnType::default())
+    }
+
+    fn apply_with<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+        installed_on_type: InstalledOnType,
     ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
         Box::pin(async move {
             let start = Instant::now();
 
             // execute migration queries
             if migration.no_tx {
-                execute_migration(self, table_name, migration).await?;
+                execute_migration(self, table_name, migration, installed_on_type).await?;
             } else {
                 // Use a single transaction for the actual migration script and the essential bookeeping so we never
                 // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
@@ -239,7 +271,7 @@ CREATE TABLE IF NOT EXISTS {table_name} (
                 // data lineage and debugging reasons, so it is not super important if it is lost. So we initialize it to -1
                 // and update it once the actual transaction completed.
                 let mut tx = self.begin().await?;
-                execute_migration(&mut tx, table_name, migration).await?;
+                execute_migration(&mut tx, table_name, migration, installed_on_type).await?;
                 tx.commit().await?;
             }
 
@@ -296,18 +328,18 @@ async fn execute_migration(
     conn: &mut PgConnection,
     table_name: &str,
     migration: &Migration,
+    installed_on_type: InstalledOnType,
 ) -> Result<(), MigrateError> {
     let _ = conn
         .execute(migration.sql.clone())
         .await
         .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
 
-    // language=SQL
-    let _ = query(AssertSqlSafe(format!(
-        r#"
-    INSERT INTO {table_name} ( version, description, success, checksum, execution_time )
-    VALUES ( $1, $2, TRUE, $3, -1 )
-                "#
+    let _ = query(AssertSqlSafe(DatabaseKind::Postgres.insert_migration_sql(
+        table_name,
+        true,
+        installed_on_type,
+        SystemTime::now(),
     )))
     .bind(migration.version)
     .bind(&*migration.description)
@@ -14,6 +14,7 @@ use std::str::FromStr;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
+use std::time::SystemTime;
 
 pub(crate) use sqlx_core::migrate::*;
 use sqlx_core::query_scalar::query_scalar;
@@ -85,21 +86,19 @@ impl Migrate for SqliteConnection {
     fn ensure_migrations_table<'e>(
         &'e mut self,
         table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        self.ensure_migrations_table_with(table_name, InstalledOnType::default())
+    }
+
+    fn ensure_migrations_table_with<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        installed_on_type: InstalledOnType,
     ) -> BoxFuture<'e, Result<(), MigrateError>> {
         Box::pin(async move {
-            // language=SQLite
-            self.execute(AssertSqlSafe(format!(
-                r#"
-CREATE TABLE IF NOT EXISTS {table_name} (
-    version BIGINT PRIMARY KEY,
-    description TEXT NOT NULL,
-    installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
-    success BOOLEAN NOT NULL,
-    checksum BLOB NOT NULL,
-    execution_time BIGINT NOT NULL
-);
-                "#
-            )))
+            self.execute(AssertSqlSafe(
+                DatabaseKind::Sqlite.create_migrations_table_sql(table_name, installed_on_type),
+            ))
             .await?;
 
             Ok(())
@@ -158,12 +157,21 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         &'e mut self,
         table_name: &'e str,
         migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
+        self.apply_with(table_name, migration, InstalledOnType::default())
+    }
+
+    fn apply_with<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+        installed_on_type: InstalledOnType,
     ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
         Box::pin(async move {
             let start = Instant::now();
 
             if migration.no_tx {
-                execute_migration(self, table_name, migration).await?;
+                execute_migration(self, table_name, migration, installed_on_type).await?;
             } else {
                 // Use a single transaction for the actual migration script and the essential bookkeeping so we never
                 // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
@@ -171,7 +179,7 @@ CREATE TABLE IF NOT EXISTS {table_name} (
                 // data lineage and debugging reasons, so it is not super important if it is lost. So we initialize it to -1
                 // and update it once the actual transaction completed.
                 let mut tx = self.begin().await?;
-                execute_migration(&mut tx, table_name, migration).await?;
+                execute_migration(&mut tx, table_name, migration, installed_on_type).await?;
                 tx.commit().await?;
             }
 
@@ -207,7 +215,7 @@ CREATE TABLE IF NOT EXISTS {table_name} (
             let start = Instant::now();
 
             if migration.no_tx {
-                execute_migration(self, table_name, migration).await?;
+                execute_migration(self, table_name, migration, InstalledOnType::default()).await?;
             } else {
                 // Use a single transaction for the actual migration script and the essential bookkeeping so we never
                 // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
@@ -227,18 +235,18 @@ async fn execute_migration(
     conn: &mut SqliteConnection,
     table_name: &str,
     migration: &Migration,
+    installed_on_type: InstalledOnType,
 ) -> Result<(), MigrateError> {
     let _ = conn
         .execute(migration.sql.clone())
         .await
         .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
 
-    // language=SQLite
-    let _ = query(AssertSqlSafe(format!(
-        r#"
-    INSERT INTO {table_name} ( version, description, success, checksum, execution_time )
-    VALUES ( ?1, ?2, TRUE, ?3, -1 )
-        "#
+    let _ = query(AssertSqlSafe(DatabaseKind::Sqlite.insert_migration_sql(
+        table_name,
+        true,
+        installed_on_type,
+        SystemTime::now(),
     )))
     .bind(migration.version)
     .bind(&*migration.description)
@@ -0,0 +1,4 @@
+-- Saved as Latin-1: caf
+CREATE TABLE cafes (
+    id BIGINT PRIMARY KEY
+);
@@ -0,0 +1,4 @@
+CREATE TABLE users (
+    id BIGINT PRIMARY KEY,
+    account_id BIGINT NOT NULL REFERENCES accounts (id)
+);
@@ -0,0 +1,3 @@
+CREATE TABLE posts (
+    id BIGINT PRIMARY KEY
+);
@@ -0,0 +1,4 @@
+-- 0004 was deleted
+CREATE TABLE posts (
+    id BIGINT PRIMARY KEY
+);
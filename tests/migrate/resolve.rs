@@ -0,0 +1,289 @@
+use sqlx::migrate::{
+    resolve_blocking_with_diagnostics, Diagnostics, Location, Migrator, NonUtf8Policy,
+    ResolveConfig, ResolveWith, VersioningConsistency,
+};
+use std::path::Path;
+
+fn recursive() -> ResolveConfig {
+    let mut config = ResolveConfig::new();
+    config.set_recursive(true);
+    config
+}
+
+#[sqlx_macros::test]
+async fn recursive_discovery() -> anyhow::Result<()> {
+    let migrator = Migrator::new(ResolveWith(
+        "tests/migrate/migrations_recursive",
+        recursive(),
+    ))
+    .await?;
+
+    let mut migrations: Vec<_> = migrator
+        .iter()
+        .map(|m| (m.version, &*m.description, m.migration_type.label()))
+        .collect();
+
+    // The relative order of the up and down migrations is unspecified.
+    migrations.sort();
+
+    assert_eq!(
+        migrations,
+        [
+            (20231201000000, "create accounts", "migrate"),
+            (20240101000000, "create users", "migrate"),
+            (20250101000000, "create posts", "migrate"),
+            (20250101000000, "create posts", "revert"),
+        ]
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn non_recursive_ignores_subdirectories() -> anyhow::Result<()> {
+    let migrator = Migrator::new(ResolveWith(
+        "tests/migrate/migrations_recursive",
+        ResolveConfig::new(),
+    ))
+    .await?;
+
+    let versions: Vec<_> = migrator.iter().map(|m| m.version).collect();
+
+    assert_eq!(versions, [20231201000000]);
+
+    Ok(())
+}
+
+#[test]
+fn recursive_skips_non_migration_files() -> anyhow::Result<()> {
+    let mut diagnostics = Diagnostics::new();
+
+    let migrations = resolve_blocking_with_diagnostics(
+        Path::new("tests/migrate/migrations_recursive"),
+        &recursive(),
+        &mut diagnostics,
+    )?;
+
+    assert_eq!(migrations.len(), 4);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert!(!diagnostics.has_errors());
+
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert!(
+        matches!(
+            &diagnostic.location,
+            Some(Location::File(path)) if path.ends_with("fixtures/seed_data.sql")
+        ),
+        "{diagnostic:?}"
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn recursive_duplicate_versions() -> anyhow::Result<()> {
+    let err = Migrator::new(ResolveWith(
+        "tests/migrate/migrations_recursive_duplicate",
+        recursive(),
+    ))
+    .await
+    .unwrap_err();
+
+    let message = err.to_string();
+
+    assert!(
+        message.contains("duplicate migration version 20240101000000"),
+        "{message}"
+    );
+
+    Ok(())
+}
+
+fn versioning_consistency(versioning_consistency: VersioningConsistency) -> ResolveConfig {
+    let mut config = ResolveConfig::new();
+    config.set_versioning_consistency(versioning_consistency);
+    config
+}
+
+#[sqlx_macros::test]
+async fn versioning_consistency_homogeneous() -> anyhow::Result<()> {
+    let migrator = Migrator::new(ResolveWith(
+        "tests/migrate/migrations_simple",
+        versioning_consistency(VersioningConsistency::Error),
+    ))
+    .await?;
+
+    assert_eq!(migrator.iter().count(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn versioning_consistency_consecutive_timestamps() -> anyhow::Result<()> {
+    // Timestamps one second apart are still timestamps.
+    let migrator = Migrator::new(ResolveWith(
+        "tests/migrate/migrations_consecutive_timestamps",
+        versioning_consistency(VersioningConsistency::Error),
+    ))
+    .await?;
+
+    assert_eq!(migrator.iter().count(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn versioning_consistency_sequential_gaps() -> anyhow::Result<()> {
+    // Sequential versions not starting at 1, with a gap where a migration was deleted.
+    let migrator = Migrator::new(ResolveWith(
+        "tests/migrate/migrations_sequential_gaps",
+        versioning_consistency(VersioningConsistency::Error),
+    ))
+    .await?;
+
+    assert_eq!(migrator.iter().count(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn versioning_consistency_mixed_warn() -> anyhow::Result<()> {
+    let mut diagnostics = Diagnostics::new();
+
+    let migrations = resolve_blocking_with_diagnostics(
+        Path::new("tests/migrate/migrations_mixed_versioning"),
+        &versioning_consistency(VersioningConsistency::Warn),
+        &mut diagnostics,
+    )?;
+
+    assert_eq!(migrations.len(), 2);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert!(!diagnostics.has_errors());
+
+    let message = &diagnostics.iter().next().unwrap().message;
+    assert!(message.contains("mix timestamp versions"), "{message}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn versioning_consistency_mixed_warn_migrator() -> anyhow::Result<()> {
+    let migrator = Migrator::new(ResolveWith(
+        "tests/migrate/migrations_mixed_versioning",
+        versioning_consistency(VersioningConsistency::Warn),
+    ))
+    .await?;
+
+    assert_eq!(migrator.iter().count(), 2);
+
+    let diagnostics = migrator.diagnostics();
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+
+    let message = &diagnostics.iter().next().unwrap().message;
+    assert!(message.contains("mix timestamp versions"), "{message}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn versioning_consistency_mixed_error() -> anyhow::Result<()> {
+    let err = Migrator::new(ResolveWith(
+        "tests/migrate/migrations_mixed_versioning",
+        versioning_consistency(VersioningConsistency::Error),
+    ))
+    .await
+    .unwrap_err();
+
+    let message = err.to_string();
+
+    assert!(
+        message.contains(
+            "mix timestamp versions (e.g. 20240101000000) and sequential versions (e.g. 1)"
+        ),
+        "{message}"
+    );
+
+    Ok(())
+}
+
+fn non_utf8_policy(non_utf8_policy: NonUtf8Policy) -> ResolveConfig {
+    let mut config = ResolveConfig::new();
+    config.set_non_utf8_policy(non_utf8_policy);
+    config
+}
+
+#[sqlx_macros::test]
+async fn non_utf8_error() -> anyhow::Result<()> {
+    let err = Migrator::new(ResolveWith(
+        "tests/migrate/migrations_non_utf8",
+        non_utf8_policy(NonUtf8Policy::Error),
+    ))
+    .await
+    .unwrap_err();
+
+    let message = err.to_string();
+
+    assert!(
+        message.contains("20240102000000_create_cafes.sql is not valid UTF-8"),
+        "{message}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn non_utf8_skip() -> anyhow::Result<()> {
+    let mut diagnostics = Diagnostics::new();
+
+    let migrations = resolve_blocking_with_diagnostics(
+        Path::new("tests/migrate/migrations_non_utf8"),
+        &non_utf8_policy(NonUtf8Policy::Skip),
+        &mut diagnostics,
+    )?;
+
+    let versions: Vec<_> = migrations.iter().map(|(m, _)| m.version).collect();
+    assert_eq!(versions, [20240101000000]);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert!(!diagnostics.has_errors());
+
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert!(
+        diagnostic.message.contains("not valid UTF-8"),
+        "{diagnostic}"
+    );
+    assert_eq!(
+        diagnostic.location,
+        Some(Location::File(
+            Path::new("tests/migrate/migrations_non_utf8/20240102000000_create_cafes.sql")
+                .canonicalize()?
+        ))
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn non_utf8_skip_migrator() -> anyhow::Result<()> {
+    let migrator = Migrator::new(ResolveWith(
+        "tests/migrate/migrations_non_utf8",
+        non_utf8_policy(NonUtf8Policy::Skip),
+    ))
+    .await?;
+
+    let versions: Vec<_> = migrator.iter().map(|m| m.version).collect();
+    assert_eq!(versions, [20240101000000]);
+
+    let diagnostics = migrator.diagnostics();
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+
+    let diagnostic = diagnostics.iter().next().unwrap();
+    assert!(
+        diagnostic.message.contains("not valid UTF-8"),
+        "{diagnostic}"
+    );
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use sqlx::migrate::Migrator;
+use sqlx::migrate::{Diagnostics, InstalledOnType, Migrator};
 use sqlx::pool::PoolConnection;
 use sqlx::postgres::{PgConnection, Postgres};
 use sqlx::Executor;
@@ -85,6 +85,28 @@ async fn no_tx(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(migrations = false)]
+async fn installed_on_type_mismatch(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    // creates the table with the default `TIMESTAMPTZ` column
+    Migrator::with_migrations(vec![]).run(&mut conn).await?;
+
+    let mut migrator = Migrator::with_migrations(vec![]);
+    migrator.set_installed_on_type(InstalledOnType::Timestamp);
+
+    let mut diagnostics = Diagnostics::new();
+    migrator
+        .run_with_diagnostics(&mut conn, &mut diagnostics)
+        .await?;
+
+    assert_eq!(diagnostics.len(), 1);
+    let message = &diagnostics.iter().next().unwrap().message;
+    assert!(message.contains("TIMESTAMPTZ"), "{message}");
+
+    Ok(())
+}
+
 /// Ensure that we have a clean initial state.
 async fn clean_up(conn: &mut PgConnection) -> anyhow::Result<()> {
     conn.execute("DROP DATABASE IF EXISTS test_db").await.ok();
@@ -1,8 +1,9 @@
-use sqlx::migrate::Migrator;
+use sqlx::migrate::{Diagnostics, Migration, MigrationType, Migrator};
 use sqlx::pool::PoolConnection;
 use sqlx::sqlite::{Sqlite, SqliteConnection};
 use sqlx::Executor;
 use sqlx::Row;
+use sqlx::SqlSafeStr;
 use std::path::Path;
 
 #[sqlx::test(migrations = false)]
@@ -77,6 +78,43 @@ async fn no_tx(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(migrations = false)]
+async fn out_of_order(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let migration = |version| {
+        Migration::new(
+            version,
+            "select one".into(),
+            MigrationType::Simple,
+            "SELECT 1".into_sql_str(),
+            false,
+        )
+    };
+
+    // apply migrations 1 and 3
+    Migrator::with_migrations(vec![migration(1), migration(3)])
+        .run(&mut conn)
+        .await?;
+
+    // migration 2 appears later, e.g. after merging another branch
+    let mut diagnostics = Diagnostics::new();
+
+    Migrator::with_migrations(vec![migration(1), migration(2), migration(3)])
+        .run_with_diagnostics(&mut conn, &mut diagnostics)
+        .await?;
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+
+    let message = &diagnostics.iter().next().unwrap().message;
+    assert!(
+        message.contains("migration 2 is older than the latest applied migration 3"),
+        "{message}"
+    );
+
+    Ok(())
+}
+
 /// Ensure that we have a clean initial state.
 async fn clean_up(conn: &mut SqliteConnection) -> anyhow::Result<()> {
     conn.execute("DROP TABLE migrations_simple_test").await.ok();